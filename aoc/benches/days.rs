@@ -0,0 +1,50 @@
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc::inputs::InputManager;
+use aoc::solve::{visit_day, Visitor};
+use aoc::verify::list_inputs;
+use common::Solution;
+
+// Benchmark group named after the input, parse and each part are measured
+// separately
+struct Bench<'a> {
+    criterion: &'a mut Criterion,
+    name: &'a str,
+    input: &'a str,
+}
+
+impl Visitor for Bench<'_> {
+    type Output = ();
+
+    fn visit<S: Solution>(self) {
+        let input = self.input;
+        let mut group = self.criterion.benchmark_group(self.name);
+        group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+        if let Ok(parsed) = S::parse(input) {
+            group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+            group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+        }
+        group.finish();
+    }
+}
+
+fn days(criterion: &mut Criterion) {
+    let inputs = InputManager::from_env();
+    let input_dir = inputs.dir();
+    for (day, name) in list_inputs(input_dir).unwrap() {
+        let input = fs::read_to_string(input_dir.join(&name)).unwrap();
+        visit_day(
+            day,
+            Bench {
+                criterion,
+                name: &name,
+                input: &input,
+            },
+        );
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// Generate a test for each file of the dayN/examples directories, included
+// by tests/examples.rs
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut tests = String::new();
+    // A new day is added to the workspace members. Cargo scans a watched
+    // directory recursively, so a day is only watched until it has an
+    // examples directory, then the examples directory is
+    println!(
+        "cargo:rerun-if-changed={}",
+        root.join("Cargo.toml").display()
+    );
+    for day in 1..=25 {
+        let day_dir = root.join(format!("day{}", day));
+        let dir = day_dir.join("examples");
+        if !dir.is_dir() {
+            if day_dir.is_dir() {
+                println!("cargo:rerun-if-changed={}", day_dir.display());
+            }
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let mut files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            // Hidden files such as .gitkeep are not examples
+            .filter(|p| p.is_file() && !p.file_name().unwrap().to_string_lossy().starts_with('.'))
+            .collect();
+        files.sort();
+        for path in files {
+            let name: String = path
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            let path = path.canonicalize().unwrap();
+            writeln!(
+                tests,
+                "#[test]\nfn day{}_{}() {{\n    \
+                 if let Err(e) = aoc::examples::check_example({}, include_str!({:?})) {{\n        \
+                 panic!(\"{{}}\", e);\n    }}\n}}\n",
+                day,
+                name.to_lowercase(),
+                day,
+                path
+            )
+            .unwrap();
+        }
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
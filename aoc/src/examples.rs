@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use toml::value::Table;
+
+use crate::solve::solve;
+use crate::verify::parse_parts;
+
+const SEPARATOR: &str = "\n---\n";
+
+/// Example of a day, its expected answers are given before the input as
+/// part1 = ... and part2 = ... lines ended by a --- line
+#[derive(Debug, PartialEq)]
+pub struct Example<'a> {
+    pub answers: BTreeMap<u8, String>,
+    pub input: &'a str,
+}
+
+pub fn parse_example(text: &str) -> common::Result<Example<'_>> {
+    let (header, input) = match text.find(SEPARATOR) {
+        Some(i) => (&text[..i], &text[i + SEPARATOR.len()..]),
+        None => return Err("no --- line after the answers".into()),
+    };
+    let table: Table = toml::from_str(header).map_err(|e| e.to_string())?;
+    let answers = parse_parts(&table).map_err(|key| format!("unknown key {}", key))?;
+    if answers.is_empty() {
+        return Err("no answer before the --- line".into());
+    }
+    Ok(Example { answers, input })
+}
+
+// Solve the parts of the example that have an answer, all the wrong answers
+// are reported in the error
+pub fn check_example(day: u8, text: &str) -> common::Result<()> {
+    let example = parse_example(text)?;
+    let parts: Vec<u8> = example.answers.keys().copied().collect();
+    let mut errors = vec![];
+    for part in solve(day, example.input, &parts)?.parts {
+        let expected = &example.answers[&part.part];
+        match part.answer {
+            Ok(answer) if answer == *expected => {}
+            Ok(answer) => errors.push(format!(
+                "part {}: expected {}, got {}",
+                part.part, expected, answer
+            )),
+            Err(e) => errors.push(format!("part {}: {}", part.part, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n").into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_example() {
+        let example = parse_example("# comment\npart2 = 8\n---\nnop +0\n---\n").unwrap();
+        assert_eq!(example.answers, vec![(2, "8".into())].into_iter().collect());
+        assert_eq!(example.input, "nop +0\n---\n");
+        assert!(parse_example("part1 = 1\nnop +0\n").is_err());
+        assert!(parse_example("part3 = 1\n---\nnop +0\n").is_err());
+        assert!(parse_example("---\nnop +0\n").is_err());
+    }
+
+    #[test]
+    fn test_check_example() {
+        assert!(check_example(1, "part1 = 514579\n---\n1721\n299\n").is_ok());
+        assert_eq!(
+            check_example(1, "part1 = 1\npart2 = 2\n---\n1721\n299\n")
+                .unwrap_err()
+                .to_string(),
+            "part 1: expected 1, got 514579\npart 2: no solution found"
+        );
+    }
+}
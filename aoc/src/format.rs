@@ -0,0 +1,172 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::solve::{Outcome, PartOutcome};
+
+/// Output of the answers, text for people and json lines for dashboards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {}, expected json or text", s)),
+        }
+    }
+}
+
+// A line for each part of the outcome
+pub fn lines(format: Format, day: u8, outcome: &Outcome) -> Vec<String> {
+    outcome
+        .parts
+        .iter()
+        .map(|part| match format {
+            Format::Text => match &part.answer {
+                Ok(answer) => format!("Part {}: {}", part.part, answer),
+                Err(e) => format!("Part {}: error: {}", part.part, e),
+            },
+            Format::Json => json_line(day, outcome, part),
+        })
+        .collect()
+}
+
+// Lines of every day run by run --all, text is a table ended by the total
+// time and json has a line for each part, or for the day when it failed
+pub fn all_lines(
+    format: Format,
+    results: &[(u8, common::Result<Outcome>)],
+    wall: Duration,
+) -> Vec<String> {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let mut lines = vec![];
+    if format == Format::Text {
+        lines.push(format!(
+            "{:<4} {:<4} {:>12} {:>12}  answer",
+            "day", "part", "parse", "solve"
+        ));
+    }
+    let mut total = Duration::default();
+    for (day, outcome) in results {
+        let outcome = match (format, outcome) {
+            (Format::Text, Err(e)) => {
+                lines.push(format!(
+                    "{:<4} {:<4} {:>12} {:>12}  error: {}",
+                    day, "-", "-", "-", e
+                ));
+                continue;
+            }
+            (Format::Json, Err(e)) => {
+                lines.push(json!({ "day": day, "error": e.to_string() }).to_string());
+                continue;
+            }
+            (_, Ok(outcome)) => outcome,
+        };
+        total += outcome.parse;
+        for part in &outcome.parts {
+            total += part.duration;
+            lines.push(match format {
+                Format::Text => format!(
+                    "{:<4} {:<4} {:>10.3}ms {:>10.3}ms  {}",
+                    day,
+                    part.part,
+                    ms(outcome.parse),
+                    ms(part.duration),
+                    match &part.answer {
+                        Ok(answer) => answer.clone(),
+                        Err(e) => format!("error: {}", e),
+                    }
+                ),
+                Format::Json => json_line(*day, outcome, part),
+            });
+        }
+    }
+    if format == Format::Text {
+        lines.push(format!(
+            "total {:.3}ms wall time, {:.3}ms summed over the days",
+            ms(wall),
+            ms(total)
+        ));
+    }
+    lines
+}
+
+// {"day", "part", "answer", "parse_ns", "solve_ns"}, the answer is always a
+// string and null with an "error" message when the part failed
+fn json_line(day: u8, outcome: &Outcome, part: &PartOutcome) -> String {
+    let mut line = json!({
+        "day": day,
+        "part": part.part,
+        "answer": part.answer.as_ref().ok(),
+        "parse_ns": outcome.parse.as_nanos() as u64,
+        "solve_ns": part.duration.as_nanos() as u64,
+    });
+    if let Err(e) = &part.answer {
+        line["error"] = e.to_string().into();
+    }
+    line.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let outcome = Outcome {
+            parse: Duration::from_nanos(1500),
+            parts: vec![
+                PartOutcome {
+                    part: 1,
+                    answer: Ok("514579".into()),
+                    duration: Duration::from_nanos(20),
+                },
+                PartOutcome {
+                    part: 2,
+                    answer: common::no_solution(),
+                    duration: Duration::from_nanos(30),
+                },
+            ],
+        };
+        assert_eq!(
+            lines(Format::Text, 1, &outcome),
+            vec!["Part 1: 514579", "Part 2: error: no solution found"]
+        );
+        assert_eq!(
+            lines(Format::Json, 1, &outcome),
+            vec![
+                r#"{"answer":"514579","day":1,"parse_ns":1500,"part":1,"solve_ns":20}"#,
+                r#"{"answer":null,"day":1,"error":"no solution found","parse_ns":1500,"part":2,"solve_ns":30}"#
+            ]
+        );
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+
+        let results = vec![(1, Ok(outcome)), (2, Err(common::Error::parse(3, "x")))];
+        let wall = Duration::from_micros(1500);
+        assert_eq!(
+            all_lines(Format::Text, &results, wall),
+            vec![
+                "day  part        parse        solve  answer",
+                "1    1         0.002ms      0.000ms  514579",
+                "1    2         0.002ms      0.000ms  error: no solution found",
+                "2    -               -            -  error: line 3: x",
+                "total 1.500ms wall time, 0.002ms summed over the days",
+            ]
+        );
+        assert_eq!(
+            all_lines(Format::Json, &results, wall)[2],
+            r#"{"day":2,"error":"line 3: x"}"#
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use common::Error;
+use sha2::{Digest, Sha256};
+
+const CHECKSUMS: &str = "checksums.toml";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Finds the input of a day in a directory, downloading it from a mirror of
+/// the puzzle inputs when it's missing
+pub struct InputManager {
+    dir: PathBuf,
+    mirror: Option<String>,
+    session: Option<String>,
+    // Limit of the connection to the mirror and of each read or write
+    timeout: Duration,
+    // Held while checksums.toml is read or rewritten, the days are loaded
+    // on parallel threads
+    checksums_lock: Mutex<()>,
+}
+
+impl InputManager {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputManager {
+            dir: dir.into(),
+            mirror: None,
+            session: None,
+            timeout: TIMEOUT,
+            checksums_lock: Mutex::new(()),
+        }
+    }
+
+    // Base url of the mirror, inputs are fetched from <url>/2020/day/N/input
+    // with the session token sent as a cookie
+    pub fn with_mirror(mut self, url: &str, session: Option<String>) -> Self {
+        self.mirror = Some(url.trim_end_matches('/').to_string());
+        self.session = session;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // AOC_INPUT_DIR, input/ by default, AOC_MIRROR and AOC_SESSION
+    pub fn from_env() -> Self {
+        let dir = env::var("AOC_INPUT_DIR")
+            .unwrap_or_else(|_| format!("{}/../input", env!("CARGO_MANIFEST_DIR")));
+        let manager = InputManager::new(dir);
+        match env::var("AOC_MIRROR") {
+            Ok(url) => manager.with_mirror(&url, env::var("AOC_SESSION").ok()),
+            Err(_) => manager,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // Input dN of the day, a downloaded input is checked against the
+    // checksum recorded with it and downloaded again if it doesn't match
+    pub fn load(&self, day: u8) -> common::Result<String> {
+        let name = format!("d{}", day);
+        let path = self.dir.join(&name);
+        let checksums = self.locked(|| self.checksums())?;
+        if path.exists() {
+            let input = fs::read_to_string(&path)?;
+            match checksums.get(&name) {
+                Some(sum) if *sum != checksum(&input) && self.mirror.is_none() => {
+                    return Err(format!("{}: checksum mismatch", path.display()).into())
+                }
+                Some(sum) if *sum != checksum(&input) => {}
+                _ => return Ok(input),
+            }
+        }
+        let mirror = match &self.mirror {
+            Some(mirror) => mirror,
+            None => {
+                return Err(Error::Io(format!(
+                    "{}: not found and AOC_MIRROR is not set",
+                    path.display()
+                )))
+            }
+        };
+        let url = format!("{}/2020/day/{}/input", mirror, day);
+        let input = get(&url, self.session.as_deref(), self.timeout)
+            .map_err(|e| Error::Io(format!("{}: {}", url, e)))?;
+
+        // Write to a temporary file first so an interrupted download is never
+        // taken for the input
+        fs::create_dir_all(&self.dir)?;
+        let partial = self.dir.join(format!(".{}.partial", name));
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+        // Read again, another thread may have recorded its download since
+        self.locked(|| {
+            let mut checksums = self.checksums()?;
+            checksums.insert(name, checksum(&input));
+            let checksums = toml::to_string(&checksums).map_err(|e| e.to_string())?;
+            fs::write(self.dir.join(CHECKSUMS), checksums)?;
+            Ok(())
+        })?;
+        Ok(input)
+    }
+
+    fn locked<T>(&self, f: impl FnOnce() -> common::Result<T>) -> common::Result<T> {
+        let _guard = self
+            .checksums_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f()
+    }
+
+    fn checksums(&self) -> common::Result<BTreeMap<String, String>> {
+        let path = self.dir.join(CHECKSUMS);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let text = fs::read_to_string(&path)?;
+        Ok(toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?)
+    }
+}
+
+fn checksum(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+// Body of a plain http GET, the request is HTTP/1.0 so the body is never
+// chunked and ends with the connection. A stalled mirror fails after the
+// timeout
+fn get(url: &str, session: Option<&str>, timeout: Duration) -> common::Result<String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or("only http:// mirrors are supported")?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let timed_out = |e: io::Error| match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+            Error::Io(format!("timed out after {:?}", timeout))
+        }
+        _ => Error::from(e),
+    };
+    let mut stream = connect(&address, timeout).map_err(timed_out)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: aoc-runner\r\n",
+        path, host
+    );
+    if let Some(session) = session {
+        request.push_str(&format!("Cookie: session={}\r\n", session));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).map_err(timed_out)?;
+    let mut response = vec![];
+    stream.read_to_end(&mut response).map_err(timed_out)?;
+
+    let response = String::from_utf8(response).map_err(|_| "response is not utf-8")?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("malformed http response")?;
+    let status = head.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(format!("unexpected response {:?}", status).into()),
+    }
+}
+
+// First address of the host that accepts a connection within the timeout
+fn connect(address: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no address for the host");
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    // Serve one response on a local port, the request is sent back on join
+    fn serve(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/mirror", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 256];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, server)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_download() {
+        let dir = temp_dir("download");
+        let (url, server) = serve("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1\n2\n3\n");
+        let manager = InputManager::new(&dir).with_mirror(&url, Some("token".into()));
+        assert_eq!(manager.load(1).unwrap(), "1\n2\n3\n");
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /mirror/2020/day/1/input HTTP/1.0\r\n"));
+        assert!(request.contains("Cookie: session=token\r\n"));
+
+        // Cached on disk, the mirror is not called again
+        assert_eq!(fs::read_to_string(dir.join("d1")).unwrap(), "1\n2\n3\n");
+        let checksums = fs::read_to_string(dir.join(CHECKSUMS)).unwrap();
+        assert!(checksums.contains(&checksum("1\n2\n3\n")));
+        assert_eq!(InputManager::new(&dir).load(1).unwrap(), "1\n2\n3\n");
+
+        // A corrupted input is downloaded again
+        fs::write(dir.join("d1"), "1\n2\n").unwrap();
+        assert!(InputManager::new(&dir).load(1).is_err());
+        let (url, server) = serve("HTTP/1.1 200 OK\r\n\r\n1\n2\n3\n");
+        let manager = InputManager::new(&dir).with_mirror(&url, None);
+        assert_eq!(manager.load(1).unwrap(), "1\n2\n3\n");
+        assert!(!server.join().unwrap().contains("Cookie"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Every day downloaded in parallel keeps its checksum
+    #[test]
+    fn test_parallel_downloads() {
+        let dir = temp_dir("parallel");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            for stream in listener.incoming().take(8) {
+                let mut stream = stream.unwrap();
+                let mut request = vec![];
+                let mut buffer = [0; 256];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                let day = request.split('/').nth(3).unwrap();
+                write!(stream, "HTTP/1.0 200 OK\r\n\r\n{}\n", day).unwrap();
+            }
+        });
+        let manager = InputManager::new(&dir).with_mirror(&url, None);
+        thread::scope(|scope| {
+            for day in 1..=8 {
+                let manager = &manager;
+                scope.spawn(move || assert_eq!(manager.load(day).unwrap(), format!("{}\n", day)));
+            }
+        });
+        server.join().unwrap();
+        let checksums = InputManager::new(&dir).checksums().unwrap();
+        assert_eq!(checksums.len(), 8);
+        assert_eq!(checksums["d5"], checksum("5\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing() {
+        let dir = temp_dir("missing");
+        assert!(InputManager::new(&dir).load(2).is_err());
+        let (url, server) = serve("HTTP/1.1 404 Not Found\r\n\r\nnot found");
+        let error = InputManager::new(&dir)
+            .with_mirror(&url, None)
+            .load(2)
+            .unwrap_err();
+        server.join().unwrap();
+        assert!(error
+            .to_string()
+            .ends_with("unexpected response \"HTTP/1.1 404 Not Found\""));
+        assert!(!dir.join("d2").exists());
+    }
+
+    // A mirror that accepts the connection and never answers
+    #[test]
+    fn test_timeout() {
+        let dir = temp_dir("timeout");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let manager = InputManager::new(&dir)
+            .with_mirror(&url, None)
+            .with_timeout(Duration::from_millis(100));
+        let error = manager.load(4).unwrap_err();
+        assert_eq!(
+            error,
+            Error::Io(format!("{}/2020/day/4/input: timed out after 100ms", url))
+        );
+        assert_eq!(error.exit_code(), 74);
+        drop(listener);
+    }
+
+    #[test]
+    fn test_local_input() {
+        let dir = temp_dir("local");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("d3"), "..#\n").unwrap();
+        assert_eq!(InputManager::new(&dir).load(3).unwrap(), "..#\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+pub mod examples;
+pub mod format;
+pub mod inputs;
+pub mod report;
+pub mod scaffold;
+pub mod solve;
+pub mod verify;
@@ -0,0 +1,215 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+use aoc::format::{self, Format};
+use aoc::inputs::InputManager;
+use aoc::solve::{implemented, solve, solve_all};
+use aoc::{report, scaffold, verify};
+
+const USAGE: &str = "Usage:
+    aoc run <day> [--part 1|2] [--input path|-] [--format text|json]
+    aoc run --all [--part 1|2] [--format text|json]
+    aoc verify
+    aoc new <day>
+    aoc bench-report [--baseline name] [--output path]";
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    // None runs all the days
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    format: Format,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run(&args[1..])
+            .map_err(|e| e.into())
+            .and_then(|options| run(&options)),
+        Some("bench-report") => bench_report(&args[1..]),
+        Some("new") if args.len() == 2 => new_day(&args[1]),
+        Some("verify") if args.len() == 1 => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+            match verify::verify(InputManager::from_env().dir(), &root) {
+                Ok(true) => Ok(()),
+                Ok(false) => process::exit(1),
+                Err(e) => Err(e),
+            }
+        }
+        _ => Err(USAGE.into()),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(e.exit_code());
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunOptions, String> {
+    let mut args = args.iter();
+    let day = match args.next().map(String::as_str) {
+        Some("--all") => None,
+        Some(day) => Some(day.parse().map_err(|_| USAGE.to_string())?),
+        None => return Err(USAGE.into()),
+    };
+    let mut options = RunOptions {
+        day,
+        part: None,
+        input: None,
+        format: Format::Text,
+    };
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--part", Some(p)) if p == "1" || p == "2" => options.part = p.parse().ok(),
+            ("--input", Some(path)) => options.input = Some(path.clone()),
+            ("--format", Some(f)) => options.format = f.parse()?,
+            _ => return Err(USAGE.into()),
+        }
+    }
+    if options.day.is_none() && options.input.is_some() {
+        return Err("--input can't be used with --all".into());
+    }
+    Ok(options)
+}
+
+fn read_input(day: u8, options: &RunOptions) -> common::Result<String> {
+    let path = match &options.input {
+        Some(path) => path,
+        None => return InputManager::from_env().load(day),
+    };
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| common::Error::Io(format!("stdin: {}", e)))?;
+        return Ok(input);
+    }
+    fs::read_to_string(path).map_err(|e| common::Error::Io(format!("{}: {}", path, e)))
+}
+
+fn run(options: &RunOptions) -> common::Result<()> {
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let day = match options.day {
+        Some(day) => day,
+        None => return run_all(options, &parts),
+    };
+    implemented(day)?;
+    let input = read_input(day, options)?;
+    let outcome = solve(day, &input, &parts)?;
+    for line in format::lines(options.format, day, &outcome) {
+        println!("{}", line);
+    }
+    match outcome.parts.into_iter().find_map(|p| p.answer.err()) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+// Run the days in parallel, fails if any day or part failed
+fn run_all(options: &RunOptions, parts: &[u8]) -> common::Result<()> {
+    let start = Instant::now();
+    let results = solve_all(&InputManager::from_env(), parts);
+    let wall = start.elapsed();
+    for line in format::all_lines(options.format, &results, wall) {
+        println!("{}", line);
+    }
+    let failed = results
+        .iter()
+        .filter(|(_, outcome)| match outcome {
+            Ok(outcome) => outcome.parts.iter().any(|p| p.answer.is_err()),
+            Err(_) => true,
+        })
+        .count();
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} days failed", n).into()),
+    }
+}
+
+fn new_day(day: &str) -> common::Result<()> {
+    let day = day.parse().map_err(|_| USAGE)?;
+    scaffold::new_day(&Path::new(env!("CARGO_MANIFEST_DIR")).join(".."), day)?;
+    println!(
+        "Created day{0}, add the examples of the puzzle in day{0}/examples/",
+        day
+    );
+    Ok(())
+}
+
+// Markdown table of the last criterion run against a saved baseline
+fn bench_report(args: &[String]) -> common::Result<()> {
+    let mut baseline = "base".to_string();
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--baseline", Some(name)) => baseline = name.clone(),
+            ("--output", Some(path)) => output = Some(path.clone()),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    let target = std::env::var("CARGO_TARGET_DIR")
+        .unwrap_or_else(|_| format!("{}/../target", env!("CARGO_MANIFEST_DIR")));
+    let estimates = report::load_estimates(&Path::new(&target).join("criterion"), &baseline)?;
+    let table = report::markdown(&estimates, &baseline);
+    match output {
+        Some(path) => fs::write(&path, table).map_err(|e| format!("{}: {}", path, e).into()),
+        None => {
+            print!("{}", table);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_run(&args("8 --part 2 --input - --format json")),
+            Ok(RunOptions {
+                day: Some(8),
+                part: Some(2),
+                input: Some("-".into()),
+                format: Format::Json,
+            })
+        );
+        assert_eq!(
+            parse_run(&args("3")),
+            Ok(RunOptions {
+                day: Some(3),
+                part: None,
+                input: None,
+                format: Format::Text,
+            })
+        );
+        assert!(parse_run(&args("3 --part 3")).is_err());
+        assert!(parse_run(&args("--part 1")).is_err());
+        assert!(parse_run(&args("1 --input")).is_err());
+        assert!(parse_run(&args("1 --format yaml")).is_err());
+        assert_eq!(parse_run(&args("--all --part 1")).unwrap().day, None);
+        assert!(parse_run(&args("--all --input d1")).is_err());
+    }
+
+    #[test]
+    fn test_run_unknown_day() {
+        // Fails before looking for the input of the day
+        assert_eq!(
+            run(&parse_run(&args("42")).unwrap()),
+            Err(common::Error::Other("day 42 is not implemented".into()))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Mean time of a benchmark in the latest run and in the baseline
+#[derive(Debug, PartialEq)]
+pub struct Estimate {
+    pub input: String,
+    pub step: String,
+    pub time: f64,
+    pub baseline: Option<f64>,
+}
+
+// Read the estimates saved by criterion under criterion_dir/<input>/<step>/,
+// the latest run is in new and the baseline in its own directory
+pub fn load_estimates(criterion_dir: &Path, baseline: &str) -> common::Result<Vec<Estimate>> {
+    let mut estimates = vec![];
+    for input in sorted_dirs(criterion_dir)? {
+        if input == "report" {
+            continue;
+        }
+        for step in sorted_dirs(&criterion_dir.join(&input))? {
+            let dir = criterion_dir.join(&input).join(&step);
+            let time = match mean(&dir.join("new"))? {
+                Some(time) => time,
+                None => continue,
+            };
+            estimates.push(Estimate {
+                input: input.clone(),
+                step,
+                time,
+                baseline: mean(&dir.join(baseline))?,
+            });
+        }
+    }
+    Ok(estimates)
+}
+
+fn sorted_dirs(dir: &Path) -> common::Result<Vec<String>> {
+    let mut dirs = vec![];
+    for entry in fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            dirs.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+// Mean time in nanoseconds from the estimates.json of a run
+fn mean(run: &Path) -> common::Result<Option<f64>> {
+    let path = run.join("estimates.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let estimates: Value = serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(estimates["mean"]["point_estimate"].as_f64())
+}
+
+pub fn markdown(estimates: &[Estimate], baseline: &str) -> String {
+    let mut table = format!(
+        "| input | step | time | {} | change |\n|---|---|---:|---:|---:|\n",
+        baseline
+    );
+    for e in estimates {
+        let (base, change) = match e.baseline {
+            Some(b) => (format_time(b), format!("{:+.1}%", (e.time - b) / b * 100.0)),
+            None => ("-".into(), "-".into()),
+        };
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            e.input,
+            e.step,
+            format_time(e.time),
+            base,
+            change
+        ));
+    }
+    table
+}
+
+fn format_time(ns: f64) -> String {
+    match ns {
+        x if x < 1e3 => format!("{:.1} ns", x),
+        x if x < 1e6 => format!("{:.1} µs", x / 1e3),
+        x if x < 1e9 => format!("{:.1} ms", x / 1e6),
+        x => format!("{:.2} s", x / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(12.0), "12.0 ns");
+        assert_eq!(format_time(45_210.0), "45.2 µs");
+        assert_eq!(format_time(3_500_000.0), "3.5 ms");
+        assert_eq!(format_time(2_000_000_000.0), "2.00 s");
+    }
+
+    #[test]
+    fn test_report() {
+        let dir = std::env::temp_dir().join(format!("aoc-report-{}", std::process::id()));
+        let write = |path: &str, mean: f64| {
+            let path = dir.join(path);
+            fs::create_dir_all(&path).unwrap();
+            let json = format!("{{\"mean\": {{\"point_estimate\": {}}}}}", mean);
+            fs::write(path.join("estimates.json"), json).unwrap();
+        };
+        write("d1/parse/new", 2000.0);
+        write("d1/parse/main", 1000.0);
+        write("d1/part1/new", 500.0);
+        fs::create_dir_all(dir.join("report")).unwrap();
+
+        let estimates = load_estimates(&dir, "main").unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            estimates,
+            vec![
+                Estimate {
+                    input: "d1".into(),
+                    step: "parse".into(),
+                    time: 2000.0,
+                    baseline: Some(1000.0),
+                },
+                Estimate {
+                    input: "d1".into(),
+                    step: "part1".into(),
+                    time: 500.0,
+                    baseline: None,
+                },
+            ]
+        );
+        assert_eq!(
+            markdown(&estimates, "main"),
+            "| input | step | time | main | change |\n\
+             |---|---|---:|---:|---:|\n\
+             | d1 | parse | 2.0 µs | 1.0 µs | +100.0% |\n\
+             | d1 | part1 | 500.0 ns | - | - |\n"
+        );
+    }
+}
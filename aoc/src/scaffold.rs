@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.in");
+const MAIN_RS: &str = include_str!("../templates/main.rs.in");
+const LIB_RS: &str = include_str!("../templates/lib.rs.in");
+
+// Create the dayN crate from the templates and wire it in the workspace, the
+// aoc dependencies and visit_day
+pub fn new_day(root: &Path, day: u8) -> common::Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {} is not a day of the advent", day).into());
+    }
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    let workspace = root.join("Cargo.toml");
+    let aoc = root.join("aoc/Cargo.toml");
+    let solve = root.join("aoc/src/solve.rs");
+    let edits = vec![
+        (workspace.clone(), add_member(&read(&workspace)?, day)?),
+        (aoc.clone(), add_dependency(&read(&aoc)?, day)?),
+        (solve.clone(), add_visit(&read(&solve)?, day)?),
+    ];
+
+    // The crate and the edited files are written next to their destination
+    // first, a failure there removes them and leaves the tree untouched.
+    // They are then renamed in place
+    let staging = root.join(format!(".day{}.new", day));
+    let staged: Vec<(PathBuf, PathBuf, String)> = edits
+        .into_iter()
+        .map(|(path, text)| (staging_path(&path), path, text))
+        .collect();
+    let written = write_day(&staging, day).and_then(|()| {
+        for (path, _, text) in &staged {
+            fs::write(path, text)?;
+        }
+        Ok(())
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_dir_all(&staging);
+        for (path, _, _) in &staged {
+            let _ = fs::remove_file(path);
+        }
+        return Err(e);
+    }
+    fs::rename(&staging, &dir)?;
+    for (path, destination, _) in staged {
+        fs::rename(path, destination)?;
+    }
+    Ok(())
+}
+
+// Files of the dayN crate, the examples directory is kept by an empty
+// .gitkeep until the first example is added
+fn write_day(dir: &Path, day: u8) -> common::Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("examples"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TOML, day))?;
+    fs::write(dir.join("src/main.rs"), render(MAIN_RS, day))?;
+    fs::write(dir.join("src/lib.rs"), render(LIB_RS, day))?;
+    fs::write(dir.join("answers.toml"), format!("[d{}]\n", day))?;
+    fs::write(dir.join("examples/.gitkeep"), "")?;
+    Ok(())
+}
+
+// .<name>.new in the directory of path
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.new", name))
+}
+
+fn read(path: &Path) -> common::Result<String> {
+    Ok(fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?)
+}
+
+fn render(template: &str, day: u8) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+// Insert "dayN" in the members of the workspace, before the closing bracket
+fn add_member(cargo_toml: &str, day: u8) -> common::Result<String> {
+    let member = format!("\"day{}\"", day);
+    if cargo_toml.contains(&member) {
+        return Err(format!("day{} is already a member of the workspace", day).into());
+    }
+    let start = cargo_toml
+        .find("members = [")
+        .ok_or("no members in Cargo.toml")?;
+    let end = start
+        + cargo_toml[start..]
+            .find(']')
+            .ok_or("no members in Cargo.toml")?;
+    Ok(format!(
+        "{}    {},\n{}",
+        &cargo_toml[..end],
+        member,
+        &cargo_toml[end..]
+    ))
+}
+
+// Insert the dependency after the last dayN dependency of aoc
+fn add_dependency(cargo_toml: &str, day: u8) -> common::Result<String> {
+    let mut lines: Vec<&str> = cargo_toml.lines().collect();
+    let last = lines
+        .iter()
+        .rposition(|l| l.starts_with("day"))
+        .ok_or("no day dependency in aoc/Cargo.toml")?;
+    let dependency = format!("day{} = {{ path = \"../day{}\" }}", day, day);
+    lines.insert(last + 1, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Add the day to visit_day and raise DAYS if needed
+fn add_visit(solve_rs: &str, day: u8) -> common::Result<String> {
+    let fallback = "        _ => return None,";
+    if !solve_rs.contains(fallback) {
+        return Err("no visit_day in aoc/src/solve.rs".into());
+    }
+    let visit = format!(
+        "        {} => visitor.visit::<day{}::Day{}>(),\n",
+        day, day, day
+    );
+    let solve_rs = solve_rs.replacen(fallback, &(visit + fallback), 1);
+
+    let days = solve_rs
+        .lines()
+        .find_map(|l| l.strip_prefix("pub const DAYS: u8 = "))
+        .and_then(|d| d.trim_end_matches(';').parse::<u8>().ok())
+        .ok_or("no DAYS in aoc/src/solve.rs")?;
+    Ok(solve_rs.replacen(
+        &format!("pub const DAYS: u8 = {};", days),
+        &format!("pub const DAYS: u8 = {};", days.max(day)),
+        1,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let cargo_toml = "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day1\",\n]\n";
+        assert_eq!(
+            add_member(cargo_toml, 2).unwrap(),
+            "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\n"
+        );
+        assert!(add_member(cargo_toml, 1).is_err());
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let cargo_toml = "[dependencies]\nday1 = { path = \"../day1\" }\ntoml = \"0.5\"\n";
+        assert_eq!(
+            add_dependency(cargo_toml, 2).unwrap(),
+            "[dependencies]\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\ntoml = \"0.5\"\n"
+        );
+    }
+
+    #[test]
+    fn test_add_visit() {
+        let solve_rs = "pub const DAYS: u8 = 1;\n\
+                        match day {\n        \
+                        1 => visitor.visit::<day1::Day1>(),\n        \
+                        _ => return None,\n}\n";
+        assert_eq!(
+            add_visit(solve_rs, 2).unwrap(),
+            "pub const DAYS: u8 = 2;\n\
+             match day {\n        \
+             1 => visitor.visit::<day1::Day1>(),\n        \
+             2 => visitor.visit::<day2::Day2>(),\n        \
+             _ => return None,\n}\n"
+        );
+    }
+
+    // A minimal workspace with the files edited for a new day
+    fn workspace(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"aoc\",\n]\n").unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "day1 = { path = \"../day1\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/solve.rs"),
+            "pub const DAYS: u8 = 1;\n        _ => return None,\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = workspace("new-day");
+        new_day(&root, 9).unwrap();
+        assert!(root.join("day9/src/lib.rs").is_file());
+        assert!(root.join("day9/examples/.gitkeep").is_file());
+        assert!(read(&root.join("Cargo.toml")).unwrap().contains("\"day9\""));
+        let mut names: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Cargo.toml", "aoc", "day9"]);
+        assert!(new_day(&root, 9).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // A failure while writing the new files leaves the tree as it was
+    #[test]
+    fn test_new_day_failure() {
+        let root = workspace("new-day-failure");
+        fs::write(root.join(".day9.new"), "in the way").unwrap();
+        let cargo_toml = read(&root.join("Cargo.toml")).unwrap();
+        assert!(new_day(&root, 9).is_err());
+        assert!(!root.join("day9").exists());
+        assert!(!root.join(".Cargo.toml.new").exists());
+        assert_eq!(read(&root.join("Cargo.toml")).unwrap(), cargo_toml);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(MAIN_RS, 9),
+            "fn main() {\n    common::main::<day9::Day9>(9);\n}\n"
+        );
+    }
+}
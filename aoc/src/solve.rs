@@ -0,0 +1,120 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::Solution;
+
+use crate::inputs::InputManager;
+
+pub const DAYS: u8 = 8;
+
+/// Answers of one day on an input, with the time spent on each step
+pub struct Outcome {
+    pub parse: Duration,
+    pub parts: Vec<PartOutcome>,
+}
+
+pub struct PartOutcome {
+    pub part: u8,
+    pub answer: common::Result<String>,
+    pub duration: Duration,
+}
+
+/// Generic code run with the solution of a day
+pub trait Visitor {
+    type Output;
+
+    fn visit<S: Solution>(self) -> Self::Output;
+}
+
+// Call the visitor with the solution of the day, None if it's not implemented
+pub fn visit_day<V: Visitor>(day: u8, visitor: V) -> Option<V::Output> {
+    Some(match day {
+        1 => visitor.visit::<day1::Day1>(),
+        2 => visitor.visit::<day2::Day2>(),
+        3 => visitor.visit::<day3::Day3>(),
+        4 => visitor.visit::<day4::Day4>(),
+        5 => visitor.visit::<day5::Day5>(),
+        6 => visitor.visit::<day6::Day6>(),
+        7 => visitor.visit::<day7::Day7>(),
+        8 => visitor.visit::<day8::Day8>(),
+        _ => return None,
+    })
+}
+
+struct Implemented;
+
+impl Visitor for Implemented {
+    type Output = ();
+
+    fn visit<S: Solution>(self) -> Self::Output {}
+}
+
+// Fails for a day without a solution, checked before its input is loaded
+pub fn implemented(day: u8) -> common::Result<()> {
+    visit_day(day, Implemented).ok_or_else(|| format!("day {} is not implemented", day).into())
+}
+
+struct Solve<'a> {
+    input: &'a str,
+    parts: &'a [u8],
+}
+
+impl Visitor for Solve<'_> {
+    type Output = common::Result<Outcome>;
+
+    fn visit<S: Solution>(self) -> Self::Output {
+        solve_with::<S>(self.input, self.parts)
+    }
+}
+
+// Parse the input and solve the requested parts of the day
+pub fn solve(day: u8, input: &str, parts: &[u8]) -> common::Result<Outcome> {
+    visit_day(day, Solve { input, parts })
+        .unwrap_or_else(|| Err(format!("day {} is not implemented", day).into()))
+}
+
+fn solve_with<S: Solution>(input: &str, parts: &[u8]) -> common::Result<Outcome> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&input).map(|a| a.to_string()),
+                _ => S::part2(&input).map(|a| a.to_string()),
+            };
+            PartOutcome {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(Outcome { parse, parts })
+}
+
+// Load and solve every day on its own thread, the outcomes are in the order
+// of the days
+pub fn solve_all(inputs: &InputManager, parts: &[u8]) -> Vec<(u8, common::Result<Outcome>)> {
+    thread::scope(|scope| {
+        let days: Vec<_> = (1..=DAYS)
+            .map(|day| {
+                let handle = scope.spawn(move || {
+                    implemented(day)?;
+                    solve(day, &inputs.load(day)?, parts)
+                });
+                (day, handle)
+            })
+            .collect();
+        days.into_iter()
+            .map(|(day, handle)| {
+                let outcome = handle
+                    .join()
+                    .unwrap_or_else(|_| Err(format!("day {} panicked", day).into()));
+                (day, outcome)
+            })
+            .collect()
+    })
+}
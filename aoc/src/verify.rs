@@ -0,0 +1,217 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use toml::value::{Table, Value};
+
+use crate::solve::{solve, DAYS};
+
+/// Expected answers of a day, keyed by input name and part
+pub type Answers = BTreeMap<String, BTreeMap<u8, String>>;
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+    Error(String),
+}
+
+// Read dayN/answers.toml, a day without the file has no expected answers
+pub fn load_answers(root: &Path, day: u8) -> common::Result<Answers> {
+    let path = root.join(format!("day{}", day)).join("answers.toml");
+    if !path.exists() {
+        return Ok(Answers::new());
+    }
+    let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_answers(&text).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+fn parse_answers(text: &str) -> common::Result<Answers> {
+    let table: Table = toml::from_str(text).map_err(|e| e.to_string())?;
+    let mut answers = Answers::new();
+    for (input, parts) in table {
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("[{}] is not a table", input))?;
+        let parts = parse_parts(parts).map_err(|key| format!("unknown key {}.{}", input, key))?;
+        if !parts.is_empty() {
+            answers.insert(input, parts);
+        }
+    }
+    Ok(answers)
+}
+
+// Answers of the parts in a table with part1 and part2 keys, an unknown key
+// is returned as the error
+pub fn parse_parts(table: &Table) -> Result<BTreeMap<u8, String>, String> {
+    let mut parts = BTreeMap::new();
+    for (key, value) in table {
+        let part = match key.as_str() {
+            "part1" => 1,
+            "part2" => 2,
+            _ => return Err(key.clone()),
+        };
+        let value = match value {
+            Value::String(s) => s.clone(),
+            v => v.to_string(),
+        };
+        parts.insert(part, value);
+    }
+    Ok(parts)
+}
+
+// Day of an input file named dN, with an optional suffix as in d8large
+pub fn input_day(name: &str) -> Option<u8> {
+    let digits: String = name
+        .strip_prefix('d')?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+// Inputs of the implemented days found in the directory, sorted by day
+pub fn list_inputs(input_dir: &Path) -> common::Result<Vec<(u8, String)>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(input_dir).map_err(|e| format!("{}: {}", input_dir.display(), e))? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        match input_day(&name) {
+            Some(day) if (1..=DAYS).contains(&day) => inputs.push((day, name)),
+            _ => {}
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn check(expected: Option<&String>, answer: &common::Result<String>) -> Status {
+    match (expected, answer) {
+        (_, Err(e)) => Status::Error(e.to_string()),
+        (None, Ok(_)) => Status::Missing,
+        (Some(e), Ok(a)) if e == a => Status::Pass,
+        (Some(e), Ok(_)) => Status::Fail(e.clone()),
+    }
+}
+
+// Run every solution on every input and report the answers against the
+// recorded ones, returns false if any answer is wrong
+pub fn verify(input_dir: &Path, root: &Path) -> common::Result<bool> {
+    let inputs = list_inputs(input_dir)?;
+
+    let mut answers = BTreeMap::new();
+    let mut count = BTreeMap::new();
+    println!(
+        "{:<10} {:<4}  {:<7} {:>12} {:>12}  answer",
+        "input", "part", "status", "parse", "solve"
+    );
+    for (day, name) in inputs {
+        if let Entry::Vacant(e) = answers.entry(day) {
+            e.insert(load_answers(root, day)?);
+        }
+        let expected = answers[&day].get(&name);
+        let input = fs::read_to_string(input_dir.join(&name))?;
+        let (parse, results) = match solve(day, &input, &[1, 2]) {
+            Ok(outcome) => (
+                outcome.parse,
+                outcome
+                    .parts
+                    .into_iter()
+                    .map(|p| {
+                        let status = check(expected.and_then(|e| e.get(&p.part)), &p.answer);
+                        (p.part, status, p.answer.unwrap_or_default(), p.duration)
+                    })
+                    .collect(),
+            ),
+            Err(e) => (
+                Duration::default(),
+                vec![(
+                    1,
+                    Status::Error(e.to_string()),
+                    String::new(),
+                    Duration::default(),
+                )],
+            ),
+        };
+        for (part, status, answer, solve) in results {
+            println!(
+                "{:<10} {:<4}  {:<7} {:>10.3}ms {:>10.3}ms  {}",
+                name,
+                part,
+                status,
+                parse.as_secs_f64() * 1000.0,
+                solve.as_secs_f64() * 1000.0,
+                answer
+            );
+            match &status {
+                Status::Fail(expected) => println!("{:>25} {}", "expected", expected),
+                Status::Error(e) => println!("{:>25} {}", "error", e),
+                _ => {}
+            }
+            *count.entry(status.to_string()).or_insert(0) += 1;
+        }
+    }
+    let total = |s: &str| count.get(s).copied().unwrap_or(0);
+    println!(
+        "{} passed, {} failed, {} missing, {} errors",
+        total("PASS"),
+        total("FAIL"),
+        total("MISSING"),
+        total("ERROR")
+    );
+    Ok(total("FAIL") == 0 && total("ERROR") == 0)
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Pass => "PASS",
+            Status::Fail(_) => "FAIL",
+            Status::Missing => "MISSING",
+            Status::Error(_) => "ERROR",
+        };
+        f.pad(status)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("[d8]\npart1 = \"1584\"\npart2 = 920\n[d8large]\n").unwrap();
+        assert_eq!(answers["d8"][&1], "1584");
+        assert_eq!(answers["d8"][&2], "920");
+        assert!(!answers.contains_key("d8large"));
+        assert!(parse_answers("[d8]\npart3 = 1\n").is_err());
+        assert!(parse_answers("d8 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_input_day() {
+        assert_eq!(input_day("d1"), Some(1));
+        assert_eq!(input_day("d8large"), Some(8));
+        assert_eq!(input_day("d12"), Some(12));
+        assert_eq!(input_day("README.md"), None);
+        assert_eq!(input_day("dx"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = "42".to_string();
+        assert_eq!(check(Some(&expected), &Ok("42".into())), Status::Pass);
+        assert_eq!(
+            check(Some(&expected), &Ok("41".into())),
+            Status::Fail("42".into())
+        );
+        assert_eq!(check(None, &Ok("41".into())), Status::Missing);
+        assert_eq!(
+            check(None, &Err("no solution found".into())),
+            Status::Error("no solution found".into())
+        );
+    }
+}
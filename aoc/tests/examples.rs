@@ -0,0 +1,2 @@
+// One test per file of dayN/examples, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
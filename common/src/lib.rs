@@ -0,0 +1,133 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::process;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error of any day, from reading the input to answering a part
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // Malformed input, line starts at 1
+    Parse { line: usize, message: String },
+    // A part without answer on the given input
+    NoSolution,
+    Io(String),
+    Other(String),
+}
+
+/// A day of the advent, parsing the input once and answering both parts
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+impl Error {
+    pub fn parse(line: usize, message: impl Display) -> Self {
+        Error::Parse {
+            line,
+            message: message.to_string(),
+        }
+    }
+
+    // Exit code of the binaries, following sysexits.h for bad input and io
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Parse { .. } => 65,
+            Error::Io(_) => 74,
+            Error::NoSolution | Error::Other(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::NoSolution => write!(f, "no solution found"),
+            Error::Io(message) | Error::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e.to_string())
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Other(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Other(message.into())
+    }
+}
+
+// Error for a part without answer on the given input
+pub fn no_solution<T>() -> Result<T> {
+    Err(Error::NoSolution)
+}
+
+// Parse every line of the input, an error is reported with its line number
+pub fn parse_lines<T, E: Display>(
+    input: &str,
+    parse: impl Fn(&str) -> std::result::Result<T, E>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| Error::parse(i + 1, format!("{:?}: {}", line, e))))
+        .collect()
+}
+
+// Entry point of the dayN binaries: read the input given as first argument,
+// input/dN by default, and print both answers
+pub fn main<S: Solution>(day: u8) {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| format!("{}/../input/d{}", env!("CARGO_MANIFEST_DIR"), day));
+    let result = fs::read_to_string(&path)
+        .map_err(Error::from)
+        .and_then(|input| S::parse(&input))
+        .and_then(|input| {
+            println!("Part 1: {}", S::part1(&input)?);
+            println!("Part 2: {}", S::part2(&input)?);
+            Ok(())
+        });
+    if let Err(e) = result {
+        eprintln!("{}: {}", path, e);
+        process::exit(e.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines("1\n2\n", str::parse::<u8>), Ok(vec![1, 2]));
+        let error = parse_lines("1\n2\nx", str::parse::<u8>).unwrap_err();
+        assert_eq!(
+            error,
+            Error::parse(3, "\"x\": invalid digit found in string")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 3: \"x\": invalid digit found in string"
+        );
+        assert_eq!(error.exit_code(), 65);
+    }
+}
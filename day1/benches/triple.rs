@@ -0,0 +1,53 @@
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion};
+
+use criterion::measurement::WallTime;
+use day1::{par_triple, part2};
+
+// Entries at 1 modulo 4 and a target multiple of 4, so only the three
+// multiples of 4 planted at the end of the report sum to it
+fn synthetic(len: usize) -> (Vec<u32>, u32) {
+    let mut state: u64 = 2020;
+    let mut data: Vec<u32> = (0..len - 3)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 38) as u32 * 4 + 1
+        })
+        .collect();
+    data.extend(&[1 << 26, (1 << 26) + 4, (1 << 27) - 4]);
+    (data, 1 << 28)
+}
+
+fn bench(group: &mut BenchmarkGroup<WallTime>, data: &[u32], target: u32, sequential: bool) {
+    if sequential {
+        group.bench_function("part2", |b| b.iter(|| part2(black_box(data), target)));
+    }
+    group.bench_function("par_triple", |b| {
+        b.iter(|| par_triple(black_box(data), target))
+    });
+}
+
+fn triple(criterion: &mut Criterion) {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../input/d1");
+    if let Ok(input) = fs::read_to_string(path) {
+        let data: Vec<u32> = input.lines().map(|l| l.parse().unwrap()).collect();
+        let mut group = criterion.benchmark_group("triple_d1");
+        bench(&mut group, &data, 2020, true);
+        group.finish();
+    }
+    // part2 is quadratic with a set per entry, it's left out of the largest
+    // report
+    for &len in &[1_000, 10_000, 100_000] {
+        let (data, target) = synthetic(len);
+        let mut group = criterion.benchmark_group(format!("triple_{}", len));
+        group.sample_size(10);
+        bench(&mut group, &data, target, len <= 10_000);
+        group.finish();
+    }
+}
+
+criterion_group!(benches, triple);
+criterion_main!(benches);
@@ -0,0 +1,188 @@
+use crate::ksum::{sorted, two_pointers};
+use crate::tuples::{all_pairs, all_triples};
+use crate::Semantics;
+
+/// Sum to look for when no tuple hits the target exactly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Closest {
+    // Highest sum not above the target
+    AtMost,
+    // Sum at the smallest distance to the target, below it on a tie
+    Nearest,
+}
+
+/// Exact match to keep when several tuples hit the target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Product {
+    Max,
+    Min,
+}
+
+// Pair of entries at distinct indices with the sum closest to target, in
+// ascending order
+pub fn closest_pair(data: &[u32], target: u32, closest: Closest) -> Option<(u32, u32)> {
+    let sorted = sorted(data);
+    let mut best = Best::new(target, closest);
+    best_pair(&sorted, 0, 0, &mut best);
+    let value = |i: usize| sorted[i] as u32;
+    best.indices.map(|i| (value(i[1]), value(i[2])))
+}
+
+pub fn closest_triple(data: &[u32], target: u32, closest: Closest) -> Option<(u32, u32, u32)> {
+    let sorted = sorted(data);
+    let mut best = Best::new(target, closest);
+    for i in 0..sorted.len() {
+        best_pair(&sorted, i, i + 1, &mut best);
+        if best.exact() {
+            break;
+        }
+    }
+    let value = |i: usize| sorted[i] as u32;
+    best.indices
+        .map(|i| (value(i[0]), value(i[1]), value(i[2])))
+}
+
+// Exact pair with the highest or lowest product
+pub fn pair_by_product(data: &[u32], target: u32, product: Product) -> Option<(u32, u32)> {
+    let pairs = all_pairs(data, target, Semantics::Distinct).into_iter();
+    let key = |&(a, b): &(u32, u32)| u128::from(a) * u128::from(b);
+    match product {
+        Product::Max => pairs.max_by_key(key),
+        Product::Min => pairs.min_by_key(key),
+    }
+}
+
+pub fn triple_by_product(data: &[u32], target: u32, product: Product) -> Option<(u32, u32, u32)> {
+    let triples = all_triples(data, target, Semantics::Distinct).into_iter();
+    let key = |&(a, b, c): &(u32, u32, u32)| u128::from(a) * u128::from(b) * u128::from(c);
+    match product {
+        Product::Max => triples.max_by_key(key),
+        Product::Min => triples.min_by_key(key),
+    }
+}
+
+// Best tuple seen so far, as indices in the sorted entries, with the first
+// one unused for a pair
+struct Best {
+    target: u64,
+    closest: Closest,
+    sum: Option<u64>,
+    indices: Option<[usize; 3]>,
+}
+
+impl Best {
+    fn new(target: u32, closest: Closest) -> Self {
+        Best {
+            target: u64::from(target),
+            closest,
+            sum: None,
+            indices: None,
+        }
+    }
+
+    fn exact(&self) -> bool {
+        self.sum == Some(self.target)
+    }
+
+    fn offer(&mut self, sum: u64, indices: [usize; 3]) {
+        // Distance to the target, a sum above it is a bit further than the
+        // sum at the same distance below
+        let distance = |s: u64| match self.closest {
+            Closest::AtMost if s > self.target => None,
+            Closest::AtMost => Some(self.target - s),
+            Closest::Nearest if s > self.target => Some((s - self.target) * 2 + 1),
+            Closest::Nearest => Some((self.target - s) * 2),
+        };
+        let better = match (distance(sum), self.sum.and_then(distance)) {
+            (Some(d), Some(best)) => d < best,
+            (d, _) => d.is_some(),
+        };
+        if better {
+            self.sum = Some(sum);
+            self.indices = Some(indices);
+        }
+    }
+}
+
+// Offer every pair the two pointers visit over sorted[from..], the sums
+// include sorted[first] when from is past it
+fn best_pair(sorted: &[u64], first: usize, from: usize, best: &mut Best) {
+    let base = if from > first { sorted[first] } else { 0 };
+    two_pointers(sorted.len(), from, |i, j| {
+        let sum = base + sorted[i] + sorted[j];
+        best.offer(sum, [first, i, j]);
+        sum.cmp(&best.target)
+    });
+}
+
+#[cfg(test)]
+mod test_closest {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::fixture::{sums, EXAMPLE};
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            closest_pair(&EXAMPLE, 2020, Closest::AtMost),
+            Some((299, 1721))
+        );
+        assert_eq!(
+            closest_pair(&EXAMPLE, 2000, Closest::AtMost),
+            Some((366, 1456))
+        );
+        assert_eq!(
+            closest_pair(&EXAMPLE, 2000, Closest::Nearest),
+            Some((299, 1721))
+        );
+        assert_eq!(closest_pair(&EXAMPLE, 500, Closest::AtMost), None);
+        assert_eq!(
+            closest_pair(&EXAMPLE, 500, Closest::Nearest),
+            Some((299, 366))
+        );
+        assert_eq!(
+            closest_triple(&EXAMPLE, 2020, Closest::Nearest),
+            Some((366, 675, 979))
+        );
+        assert_eq!(
+            closest_triple(&EXAMPLE, 1400, Closest::AtMost),
+            Some((299, 366, 675))
+        );
+        assert_eq!(closest_pair(&[1], 1, Closest::Nearest), None);
+        // A tie goes to the sum below the target
+        assert_eq!(closest_pair(&[1, 2, 4], 4, Closest::Nearest), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_product() {
+        let data = [1, 9, 4, 6, 5, 5, 0, 10];
+        assert_eq!(pair_by_product(&data, 10, Product::Max), Some((5, 5)));
+        assert_eq!(pair_by_product(&data, 10, Product::Min), Some((0, 10)));
+        assert_eq!(triple_by_product(&data, 15, Product::Max), Some((4, 5, 6)));
+        assert_eq!(triple_by_product(&data, 15, Product::Min), Some((0, 5, 10)));
+        assert_eq!(pair_by_product(&data, 100, Product::Max), None);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(
+            data in prop::collection::vec(0..100u32, 0..12),
+            target in 0..300u32,
+            at_most in any::<bool>(),
+        ) {
+            let closest = if at_most { Closest::AtMost } else { Closest::Nearest };
+            let target64 = u64::from(target);
+            let best = |sums: Vec<u64>| match closest {
+                Closest::AtMost => sums.into_iter().filter(|&s| s <= target64).max(),
+                Closest::Nearest => sums
+                    .into_iter()
+                    .min_by_key(|&s| (s.abs_diff(target64), s)),
+            };
+            let pair = closest_pair(&data, target, closest).map(|(a, b)| u64::from(a + b));
+            prop_assert_eq!(pair, best(sums(&data, 2, Semantics::Distinct)));
+            let triple = closest_triple(&data, target, closest).map(|(a, b, c)| u64::from(a + b + c));
+            prop_assert_eq!(triple, best(sums(&data, 3, Semantics::Distinct)));
+        }
+    }
+}
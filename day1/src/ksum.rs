@@ -0,0 +1,191 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+// Values of k entries of data, at distinct indices, summing to target. They
+// are returned in ascending order
+pub fn k_sum(data: &[u32], k: usize, target: u32) -> Option<Vec<u32>> {
+    let sorted = sorted(data);
+    let target = u64::from(target);
+    let indices = match k {
+        0 if target == 0 => Some(vec![]),
+        0 => None,
+        1 => sorted.binary_search(&target).ok().map(|i| vec![i]),
+        2 => two_sum(&sorted, 0, target).map(|(i, j)| vec![i, j]),
+        3 => (0..sorted.len())
+            .take_while(|&i| sorted[i] <= target)
+            .find_map(|i| two_sum(&sorted, i + 1, target - sorted[i]).map(|(j, l)| vec![i, j, l])),
+        _ => meet_in_the_middle(&sorted, k, target),
+    };
+    indices.map(|indices| indices.into_iter().map(|i| sorted[i] as u32).collect())
+}
+
+// Entries in ascending order, wide enough to add three of them
+pub(crate) fn sorted(data: &[u32]) -> Vec<u64> {
+    let mut sorted: Vec<u64> = data.iter().map(|&e| u64::from(e)).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+pub(crate) fn two_sum(sorted: &[u64], from: usize, target: u64) -> Option<(usize, usize)> {
+    two_pointers(sorted.len(), from, |i, j| {
+        (sorted[i] + sorted[j]).cmp(&target)
+    })
+}
+
+// Two pointers over the indices from..len of a sorted slice: compare tells
+// whether the sum at i and j is below or above what is looked for, and the
+// first pair where it's equal is returned
+pub(crate) fn two_pointers(
+    len: usize,
+    from: usize,
+    mut compare: impl FnMut(usize, usize) -> Ordering,
+) -> Option<(usize, usize)> {
+    let (mut i, mut j) = (from, len.checked_sub(1)?);
+    while i < j {
+        match compare(i, j) {
+            Ordering::Equal => return Some((i, j)),
+            Ordering::Less => i += 1,
+            Ordering::Greater => j -= 1,
+        }
+    }
+    None
+}
+
+// Split the k indices in the k / 2 lowest and the rest: the sums of the low
+// halves are indexed first, each keeping the half ending the earliest, then
+// every high half looks up the complement of its sum ending before it starts
+fn meet_in_the_middle(sorted: &[u64], k: usize, target: u64) -> Option<Vec<usize>> {
+    let sum = |c: &[usize]| c.iter().map(|&i| sorted[i]).sum::<u64>();
+    let mut lows: HashMap<u64, Vec<usize>> = HashMap::new();
+    combinations(sorted.len(), k / 2, |low| {
+        let s = sum(low);
+        if s <= target {
+            match lows.entry(s) {
+                Entry::Vacant(e) => {
+                    e.insert(low.to_vec());
+                }
+                Entry::Occupied(mut e) if e.get().last() > low.last() => {
+                    e.insert(low.to_vec());
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+        true
+    });
+    let mut found = None;
+    combinations(sorted.len(), k - k / 2, |high| {
+        let s = sum(high);
+        match lows.get(&target.wrapping_sub(s)) {
+            Some(low) if s <= target && low.last() < high.first() => {
+                found = Some([&low[..], high].concat());
+                false
+            }
+            _ => true,
+        }
+    });
+    found
+}
+
+// Call f with every increasing sequence of size indices below n, until it
+// returns false
+fn combinations(n: usize, size: usize, mut f: impl FnMut(&[usize]) -> bool) {
+    if size > n {
+        return;
+    }
+    let mut c: Vec<usize> = (0..size).collect();
+    while f(&c) {
+        // Move the last index that isn't at its end, the next ones follow it
+        let i = match (0..size).rev().find(|&i| c[i] < n - size + i) {
+            Some(i) => i,
+            None => return,
+        };
+        c[i] += 1;
+        for j in i + 1..size {
+            c[j] = c[j - 1] + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ksum {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::fixture::{sums, EXAMPLE};
+    use crate::{part1, part2, Semantics};
+
+    #[test]
+    fn test_example() {
+        assert_eq!(k_sum(&EXAMPLE, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(k_sum(&EXAMPLE, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(k_sum(&EXAMPLE, 4, 2319), Some(vec![299, 366, 675, 979]));
+        assert_eq!(
+            k_sum(&EXAMPLE, 6, 5496),
+            Some(vec![299, 366, 675, 979, 1456, 1721])
+        );
+        assert_eq!(k_sum(&EXAMPLE, 7, 5496), None);
+        assert_eq!(k_sum(&EXAMPLE, 1, 366), Some(vec![366]));
+        assert_eq!(k_sum(&EXAMPLE, 0, 0), Some(vec![]));
+        assert_eq!(k_sum(&[], 2, 0), None);
+    }
+
+    #[test]
+    fn test_distinct_indices() {
+        assert_eq!(k_sum(&[1010, 5], 2, 2020), None);
+        assert_eq!(k_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+        assert_eq!(k_sum(&[5, 5, 5, 1], 4, 20), None);
+        assert_eq!(k_sum(&[5, 5, 5, 5, 1], 4, 20), Some(vec![5, 5, 5, 5]));
+        assert_eq!(k_sum(&[u32::MAX; 4], 4, 0), None);
+    }
+
+    #[test]
+    fn test_combinations() {
+        let mut all = vec![];
+        combinations(4, 2, |c| {
+            all.push(c.to_vec());
+            true
+        });
+        assert_eq!(all, vec![[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_part1(data in prop::collection::vec(0..100u32, 0..30), target in 0..200u32) {
+            let pair = k_sum(&data, 2, target);
+            prop_assert_eq!(pair.is_some(), part1(&data, target).is_some());
+            if let Some(pair) = pair {
+                prop_assert_eq!(pair.iter().sum::<u32>(), target);
+            }
+        }
+
+        #[test]
+        fn matches_part2(data in prop::collection::vec(0..100u32, 0..30), target in 0..300u32) {
+            let triple = k_sum(&data, 3, target);
+            prop_assert_eq!(triple.is_some(), part2(&data, target).is_some());
+            if let Some(triple) = triple {
+                prop_assert_eq!(triple.iter().sum::<u32>(), target);
+            }
+        }
+
+        #[test]
+        fn matches_brute_force(
+            data in prop::collection::vec(0..50u32, 0..12),
+            k in 0..7usize,
+            target in 0..200u32,
+        ) {
+            let found = k_sum(&data, k, target);
+            prop_assert_eq!(found.is_some(), sums(&data, k, Semantics::Distinct).contains(&u64::from(target)));
+            if let Some(found) = found {
+                prop_assert_eq!(found.len(), k);
+                prop_assert_eq!(found.iter().sum::<u32>(), target);
+                let mut rest = data.clone();
+                for e in found {
+                    let i = rest.iter().position(|&r| r == e);
+                    prop_assert!(i.is_some());
+                    rest.swap_remove(i.unwrap());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use common::{no_solution, Result, Solution};
+
+pub mod closest;
+pub mod ksum;
+pub mod parallel;
+pub mod stream;
+pub mod subset;
+pub mod tuples;
+
+pub use ksum::k_sum;
+pub use parallel::par_triple;
+pub use stream::PairReader;
+pub use subset::subset_sum;
+
+pub struct Day1;
+
+/// How entries can be combined in a tuple
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Semantics {
+    // Every entry of the tuple is at a distinct index of the data
+    Distinct,
+    // The tuple is a multiset of the values, an entry can be used many times
+    Multiset,
+}
+
+/// Integer amount of an expense report entry
+pub trait Amount: Copy + Eq + Hash {
+    // target - self, None when it doesn't fit in the type so no amount can
+    // complete self to the target
+    fn complement(self, target: Self) -> Option<Self>;
+}
+
+macro_rules! impl_amount {
+    ($($t:ty),*) => {
+        $(impl Amount for $t {
+            fn complement(self, target: Self) -> Option<Self> {
+                target.checked_sub(self)
+            }
+        })*
+    };
+}
+
+impl_amount!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+pub fn part1<T: Amount>(data: &[T], target: T) -> Option<(T, T)> {
+    pair(data, target, Semantics::Distinct)
+}
+
+pub fn part2<T: Amount>(data: &[T], target: T) -> Option<(T, T, T)> {
+    triple(data, target, Semantics::Distinct)
+}
+
+// First pair summing to target, in the order of the data
+pub fn pair<T: Amount>(data: &[T], target: T, semantics: Semantics) -> Option<(T, T)> {
+    pair_by(data, |e| e.complement(target), semantics)
+}
+
+// First pair of entries where the first one is the complement of the
+// second, in the order of the data
+fn pair_by<T: Amount>(
+    data: &[T],
+    complement_of: impl Fn(T) -> Option<T>,
+    semantics: Semantics,
+) -> Option<(T, T)> {
+    let mut complement: HashSet<T> = HashSet::new();
+    for &e in data {
+        let c = complement_of(e);
+        if semantics == Semantics::Multiset {
+            complement.extend(c);
+        }
+        if complement.contains(&e) {
+            return complement_of(e).map(|c| (c, e));
+        }
+        complement.extend(c);
+    }
+    None
+}
+
+// First triple summing to target, the pair is searched in the entries after
+// the first one, or from it with repetition
+pub fn triple<T: Amount>(data: &[T], target: T, semantics: Semantics) -> Option<(T, T, T)> {
+    data.iter().enumerate().find_map(|(i, &e)| {
+        let rest = match semantics {
+            Semantics::Distinct => &data[i + 1..],
+            Semantics::Multiset => &data[i..],
+        };
+        let (a, b) = pair_by(rest, |b| complement_of_two(e, b, target), semantics)?;
+        Some((e, a, b))
+    })
+}
+
+// target - a - b, None when it doesn't fit in the type. When target - a
+// overflows with signed amounts, subtracting b first can stay in range
+fn complement_of_two<T: Amount>(a: T, b: T, target: T) -> Option<T> {
+    let after = |first: T, second: T| second.complement(first.complement(target)?);
+    after(a, b).or_else(|| after(b, a))
+}
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        common::parse_lines(input, str::parse::<u32>)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        match part1(input, 2020) {
+            Some((a, b)) => Ok(a as u64 * b as u64),
+            None => no_solution(),
+        }
+    }
+
+    // Sorted parallel search, large reports make the set per entry of part2
+    // too slow
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        match par_triple(input, 2020) {
+            Some((a, b, c)) => Ok(a as u64 * b as u64 * c as u64),
+            None => no_solution(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Some((1, 2)), part1(&[1, 2, 3], 3));
+        assert_eq!(Some((1, 3)), part1(&[1, 2, 3], 4));
+        assert_eq!(None, part1(&[1, 2, 3], 6));
+        assert_eq!(Some((6, 0)), part1(&[6, 0], 6));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Some((3, 6, 5)), part2(&[2, 3, 6, 5], 14));
+        assert_eq!(None, part2(&[1, 2, 3], 3));
+    }
+
+    #[test]
+    fn test_semantics() {
+        assert_eq!(pair(&[1010], 2020, Semantics::Distinct), None);
+        assert_eq!(pair(&[1010], 2020, Semantics::Multiset), Some((1010, 1010)));
+        assert_eq!(
+            pair(&[1010, 1010], 2020, Semantics::Distinct),
+            Some((1010, 1010))
+        );
+        assert_eq!(triple(&[1010, 5], 2025, Semantics::Distinct), None);
+        assert_eq!(
+            triple(&[1010, 5], 2025, Semantics::Multiset),
+            Some((1010, 1010, 5))
+        );
+        assert_eq!(triple(&[5, 1010], 15, Semantics::Multiset), Some((5, 5, 5)));
+        assert_eq!(
+            triple(&[1010, 5, 5], 1020, Semantics::Distinct),
+            Some((1010, 5, 5))
+        );
+    }
+
+    #[test]
+    fn test_part2_regressions() {
+        // An entry was used twice
+        assert_eq!(part2(&[1010, 5], 2025), None);
+        assert_eq!(part2(&[1010, 5, 5], 2020), None);
+        // target - e underflowed for an entry above the target
+        assert_eq!(part2(&[3000, 1, 2, 3], 6), Some((1, 2, 3)));
+        assert_eq!(part2(&[u32::MAX, u32::MAX, 2], 1), None);
+        assert_eq!(part2(&[], 2020), None);
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(part1(&[-5, 3, 10], -2), Some((-5, 3)));
+        assert_eq!(part1(&[-5, 3, 10], 5), Some((-5, 10)));
+        assert_eq!(part2(&[-5, 3, 10, -7], -9), Some((-5, 3, -7)));
+        assert_eq!(part2(&[-5, 3, 10, -7], 100), None);
+        assert_eq!(part1(&[i8::MIN, -1, i8::MAX], -1), Some((i8::MIN, i8::MAX)));
+        assert_eq!(part1(&[i8::MAX, 1], i8::MIN), None);
+        assert_eq!(
+            part2(&[i32::MIN, i32::MAX, -1, 0], -2),
+            Some((i32::MIN, i32::MAX, -1))
+        );
+    }
+
+    #[test]
+    fn test_wide() {
+        let data = [3_000_000_000u64, 7, 2_000_000_000];
+        assert_eq!(
+            part1(&data, 5_000_000_000),
+            Some((3_000_000_000, 2_000_000_000))
+        );
+        assert_eq!(part1(&[u64::MAX, 1], 0), None);
+        assert_eq!(part2(&[u64::MAX, 1, 0], u64::MAX), None);
+        assert_eq!(
+            part2(&[u64::MAX - 1, 1, 0], u64::MAX),
+            Some((u64::MAX - 1, 1, 0))
+        );
+        let data = [i128::MAX, i128::MIN, 1];
+        assert_eq!(part2(&data, 0), Some((i128::MAX, i128::MIN, 1)));
+        // target - e overflows but the pair brings the sum back in range
+        assert_eq!(part2(&[-100i8, 127, 73], 100), Some((-100, 127, 73)));
+        assert_eq!(part2(&[100i8, -128, -100], -128), Some((100, -128, -100)));
+        assert_eq!(
+            triple(&[i64::MIN, i64::MAX], i64::MAX - 1, Semantics::Multiset),
+            Some((i64::MIN, i64::MAX, i64::MAX))
+        );
+    }
+
+    // Small amounts, to find pairs, and the extremes of i64
+    fn amount() -> impl Strategy<Value = i64> {
+        prop_oneof![
+            -50i64..50,
+            Just(i64::MIN),
+            Just(i64::MAX),
+            Just(i64::MIN + 1)
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn signed_pair_matches_brute_force(
+            data in prop::collection::vec(amount(), 0..20),
+            target in amount(),
+            multiset in any::<bool>(),
+        ) {
+            let semantics = if multiset { Semantics::Multiset } else { Semantics::Distinct };
+            let expected = (0..data.len()).any(|i| {
+                let from = if multiset { i } else { i + 1 };
+                (from..data.len()).any(|j| i128::from(data[i]) + i128::from(data[j]) == i128::from(target))
+            });
+            let found = pair(&data, target, semantics);
+            prop_assert_eq!(found.is_some(), expected);
+            if let Some((a, b)) = found {
+                prop_assert_eq!(i128::from(a) + i128::from(b), i128::from(target));
+            }
+        }
+
+        // Any i8 so that target - e often overflows
+        #[test]
+        fn signed_triple_matches_brute_force(
+            data in prop::collection::vec(any::<i8>(), 0..12),
+            target in any::<i8>(),
+            multiset in any::<bool>(),
+        ) {
+            let semantics = if multiset { Semantics::Multiset } else { Semantics::Distinct };
+            let step = if multiset { 0 } else { 1 };
+            let sum = |a: i8, b: i8, c: i8| i32::from(a) + i32::from(b) + i32::from(c);
+            let expected = (0..data.len()).any(|i| {
+                (i + step..data.len()).any(|j| {
+                    (j + step..data.len()).any(|l| sum(data[i], data[j], data[l]) == i32::from(target))
+                })
+            });
+            let found = triple(&data, target, semantics);
+            prop_assert_eq!(found.is_some(), expected);
+            if let Some((a, b, c)) = found {
+                prop_assert_eq!(sum(a, b, c), i32::from(target));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Day1::parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        assert_eq!(Day1::part1(&input).unwrap(), 514579);
+        assert_eq!(Day1::part2(&input).unwrap(), 241861950);
+        assert!(matches!(
+            Day1::parse("1721\nabc"),
+            Err(common::Error::Parse { line: 2, .. })
+        ));
+        assert!(Day1::part1(&vec![1, 2]).is_err());
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixture {
+    use crate::Semantics;
+
+    // Report of the puzzle example
+    pub const EXAMPLE: [u32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    // Brute force oracle: every sum of size entries, at increasing indices or
+    // non decreasing ones for a multiset
+    pub fn sums(data: &[u32], size: usize, semantics: Semantics) -> Vec<u64> {
+        let step = match semantics {
+            Semantics::Distinct => 1,
+            Semantics::Multiset => 0,
+        };
+        fn sums_from(data: &[u32], from: usize, size: usize, step: usize) -> Vec<u64> {
+            if size == 0 {
+                return vec![0];
+            }
+            (from..data.len())
+                .flat_map(|i| {
+                    sums_from(data, i + step, size - 1, step)
+                        .into_iter()
+                        .map(move |s| s + u64::from(data[i]))
+                })
+                .collect()
+        }
+        sums_from(data, 0, size, step)
+    }
+}
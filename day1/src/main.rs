@@ -1,49 +1,59 @@
-use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
 
-fn part1(data: &[u32], target: u32) -> Option<(u32, u32)> {
-    let mut complement: HashSet<u32> = HashSet::new();
-    for e in data {
-        if complement.contains(e) {
-            return Some((target - e, *e));
-        }
-        if target > *e {
-            complement.insert(target - e);
-        }
-    }
-    None
-}
+use common::{Error, Solution};
+use day1::{subset_sum, Day1, PairReader};
 
-fn part2(data: &[u32], target: u32) -> Option<(u32, u32, u32)> {
-    for e in data {
-        let complement = target - e;
-        if let Some((a, b)) = part1(data, complement) {
-            return Some((*e, a, b));
-        }
+fn main() {
+    // Usage: day1 [path], day1 --stream [path|-] to stop at the first pair
+    // without reading the whole input, or day1 --subset <target> [path|-] for
+    // entries of any number summing to target
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (path, target) = match args.first().map(String::as_str) {
+        Some("--stream") => (args.get(1), None),
+        Some("--subset") => match args.get(1).map(|t| t.parse::<u32>()) {
+            Some(Ok(target)) => (args.get(2), Some(target)),
+            _ => {
+                eprintln!("usage: day1 --subset <target> [path|-]");
+                std::process::exit(1);
+            }
+        },
+        _ => return common::main::<Day1>(1),
+    };
+    let path = path
+        .cloned()
+        .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/../input/d1").into());
+    let result = open(&path).and_then(|reader| match target {
+        None => stream(reader),
+        Some(target) => subset(reader, target),
+    });
+    if let Err(e) = result {
+        eprintln!("{}: {}", path, e);
+        std::process::exit(e.exit_code());
     }
-    None
 }
 
-fn main() {
-    let input = include_str!("../../input/d1");
-    let parsed_input: Vec<u32> = input.lines().map(|e| e.parse::<u32>().unwrap()).collect();
-    println!("Part1: {:?}", part1(parsed_input.as_slice(), 2020));
-    println!("Part2: {:?}", part2(parsed_input.as_slice(), 2020));
+fn stream(reader: Box<dyn BufRead>) -> common::Result<()> {
+    let (a, b) = PairReader::new(reader, 2020)
+        .next()
+        .unwrap_or(Err(Error::NoSolution))?;
+    println!("Part 1: {}", u64::from(a) * u64::from(b));
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(Some((1, 2)), part1(&[1, 2, 3], 3));
-        assert_eq!(Some((1, 3)), part1(&[1, 2, 3], 4));
-        assert_eq!(None, part1(&[1, 2, 3], 6));
-    }
+// Entries of the subset in the order of the input, as a sum
+fn subset(mut reader: Box<dyn BufRead>, target: u32) -> common::Result<()> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let subset = subset_sum(&Day1::parse(&input)?, target)?.ok_or(Error::NoSolution)?;
+    let entries: Vec<String> = subset.iter().map(u32::to_string).collect();
+    println!("{} = {}", entries.join(" + "), target);
+    Ok(())
+}
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(Some((3, 6, 5)), part2(&[2, 3, 6, 5], 14));
-        assert_eq!(None, part2(&[1, 2, 3], 3));
+fn open(path: &str) -> common::Result<Box<dyn BufRead>> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
     }
+    Ok(Box::new(BufReader::new(File::open(path)?)))
 }
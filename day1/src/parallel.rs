@@ -0,0 +1,58 @@
+use rayon::prelude::*;
+
+use crate::ksum::two_sum;
+
+// Triple of entries at distinct indices summing to target, in ascending
+// order. The entries are sorted once, then the first entry of the triple is
+// searched in parallel, each one looking for the pair after it with two
+// pointers, so nothing is allocated per entry. The triple with the lowest
+// first entry is returned
+pub fn par_triple(data: &[u32], target: u32) -> Option<(u32, u32, u32)> {
+    let mut sorted: Vec<u64> = data.iter().map(|&e| u64::from(e)).collect();
+    sorted.par_sort_unstable();
+    let target = u64::from(target);
+    let end = sorted.partition_point(|&e| e <= target);
+    let (i, j, l) = (0..end)
+        .into_par_iter()
+        .find_map_first(|i| two_sum(&sorted, i + 1, target - sorted[i]).map(|(j, l)| (i, j, l)))?;
+    Some((sorted[i] as u32, sorted[j] as u32, sorted[l] as u32))
+}
+
+#[cfg(test)]
+mod test_parallel {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part2;
+
+    #[test]
+    fn test_example() {
+        let data = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(par_triple(&data, 2020), Some((366, 675, 979)));
+        assert_eq!(par_triple(&data, 1), None);
+        assert_eq!(par_triple(&[1010, 5, 5], 2020), None);
+        assert_eq!(par_triple(&[u32::MAX, u32::MAX, 2], 1), None);
+        assert_eq!(par_triple(&[], 0), None);
+    }
+
+    #[test]
+    fn test_large() {
+        // Only the last three entries are multiples of 4. The others are 1
+        // modulo 4, so a triple with any of them doesn't sum to a multiple
+        // of 4 such as 24
+        let mut data: Vec<u32> = (0..20_000).map(|i| 4 * i + 1).collect();
+        data.extend(&[4, 8, 12]);
+        assert_eq!(par_triple(&data, 24), Some((4, 8, 12)));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_part2(data in prop::collection::vec(0..100u32, 0..30), target in 0..300u32) {
+            let triple = par_triple(&data, target);
+            prop_assert_eq!(triple.is_some(), part2(&data, target).is_some());
+            if let Some((a, b, c)) = triple {
+                prop_assert!(a <= b && b <= c && a + b + c == target);
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use common::Error;
+
+/// Pairs of entries summing to the target, read one entry per line. A pair
+/// is yielded as soon as its second entry is read, once for every earlier
+/// entry it completes
+///
+/// Only the entries not above the target are kept, with their number of
+/// occurrences, so the memory is bounded by the target whatever the length
+/// of the input
+pub struct PairReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    target: u32,
+    seen: HashMap<u32, usize>,
+    // Earlier entries left to pair with the last one read
+    pending: Option<(u32, u32, usize)>,
+}
+
+impl<R: BufRead> PairReader<R> {
+    pub fn new(reader: R, target: u32) -> Self {
+        PairReader {
+            reader,
+            line: String::new(),
+            line_number: 0,
+            target,
+            seen: HashMap::new(),
+            pending: None,
+        }
+    }
+
+    // Line of the last entry read
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn read_entry(&mut self) -> Option<common::Result<u32>> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+            self.line_number += 1;
+            let line = self.line.trim_end_matches(&['\n', '\r'][..]);
+            if line.is_empty() {
+                continue;
+            }
+            return Some(
+                line.parse()
+                    .map_err(|e| Error::parse(self.line_number, format!("{:?}: {}", line, e))),
+            );
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PairReader<R> {
+    type Item = common::Result<(u32, u32)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((first, second, left)) = self.pending {
+                self.pending = (left > 1).then(|| (first, second, left - 1));
+                return Some(Ok((first, second)));
+            }
+            let e = match self.read_entry()? {
+                Ok(e) => e,
+                Err(e) => return Some(Err(e)),
+            };
+            let c = match self.target.checked_sub(e) {
+                Some(c) => c,
+                None => continue,
+            };
+            if let Some(&count) = self.seen.get(&c) {
+                self.pending = Some((c, e, count));
+            }
+            *self.seen.entry(e).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_stream {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_first_pair() {
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+        let mut pairs = PairReader::new(input.as_bytes(), 2020);
+        assert_eq!(pairs.next().unwrap().unwrap(), (1721, 299));
+        assert_eq!(pairs.line_number(), 4);
+        assert!(pairs.next().is_none());
+    }
+
+    #[test]
+    fn test_every_pair() {
+        let input = "5\n\n5\r\n3000\n5\n0\n10\n";
+        let pairs: Vec<_> = PairReader::new(Cursor::new(input), 10)
+            .collect::<common::Result<_>>()
+            .unwrap();
+        assert_eq!(pairs, vec![(5, 5), (5, 5), (5, 5), (0, 10)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut pairs = PairReader::new("1\n2\nx\n3\n".as_bytes(), 4);
+        assert_eq!(
+            pairs.next().unwrap(),
+            Err(Error::parse(3, "\"x\": invalid digit found in string"))
+        );
+        assert_eq!(pairs.next().unwrap(), Ok((1, 3)));
+    }
+
+    // Entries above the target are not kept
+    #[test]
+    fn test_bounded_memory() {
+        let large: String = (0..100_000).map(|i| format!("{}\n", 3000 + i)).collect();
+        let input = format!("20\n{}2000\n{}", large, large);
+        let mut pairs = PairReader::new(input.as_bytes(), 2020);
+        assert_eq!(pairs.next().unwrap(), Ok((20, 2000)));
+        assert_eq!(pairs.line_number(), 100_002);
+        assert!(pairs.next().is_none());
+        assert_eq!(pairs.seen.len(), 2);
+    }
+}
@@ -0,0 +1,125 @@
+use common::Error;
+
+/// Highest target accepted by subset_sum, the entry reaching each sum up to
+/// the target is kept on 4 bytes
+pub const MAX_TARGET: u32 = 1 << 22;
+
+// Entries at distinct indices, of any number, summing to target, in the
+// order of the data
+pub fn subset_sum(data: &[u32], target: u32) -> common::Result<Option<Vec<u32>>> {
+    if target > MAX_TARGET {
+        return Err(Error::Other(format!(
+            "target {} is above the limit of {}",
+            target, MAX_TARGET
+        )));
+    }
+    let size = target as usize + 1;
+    // Bit s of reachable is set when some of the entries read so far sum to
+    // s, and first[s] is the index of the entry that made it reachable
+    let mut reachable = vec![0u64; size.div_ceil(64)];
+    reachable[0] = 1;
+    let mut first = vec![0u32; size];
+    let is_reachable = |reachable: &[u64], s: usize| reachable[s / 64] >> (s % 64) & 1 == 1;
+    for (i, &e) in data.iter().enumerate() {
+        if e == 0 || e > target {
+            continue;
+        }
+        // reachable |= reachable << e, from the highest word so that the
+        // words shifted in are still the ones before this entry
+        let (words, bits) = ((e / 64) as usize, e % 64);
+        for w in (words..reachable.len()).rev() {
+            let mut shifted = reachable[w - words] << bits;
+            if bits > 0 && w > words {
+                shifted |= reachable[w - words - 1] >> (64 - bits);
+            }
+            let mut added = shifted & !reachable[w];
+            reachable[w] |= added;
+            while added != 0 {
+                let s = w * 64 + added.trailing_zeros() as usize;
+                if s < size {
+                    first[s] = i as u32;
+                }
+                added &= added - 1;
+            }
+        }
+        if is_reachable(&reachable, target as usize) {
+            break;
+        }
+    }
+    if !is_reachable(&reachable, target as usize) {
+        return Ok(None);
+    }
+    // The rest of a sum was reachable before its first entry, so it was
+    // reached by an earlier one
+    let mut subset = vec![];
+    let mut s = target as usize;
+    while s > 0 {
+        let e = data[first[s] as usize];
+        subset.push(e);
+        s -= e as usize;
+    }
+    subset.reverse();
+    Ok(Some(subset))
+}
+
+#[cfg(test)]
+mod test_subset {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::fixture::{sums, EXAMPLE};
+    use crate::Semantics;
+
+    // Subsets of every size
+    fn brute_force(data: &[u32], target: u32) -> bool {
+        (0..=data.len())
+            .any(|size| sums(data, size, Semantics::Distinct).contains(&u64::from(target)))
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(subset_sum(&EXAMPLE, 2020), Ok(Some(vec![1721, 299])));
+        assert_eq!(subset_sum(&EXAMPLE, 1340), Ok(Some(vec![366, 299, 675])));
+        assert_eq!(
+            subset_sum(&EXAMPLE, 5496),
+            Ok(Some(vec![1721, 979, 366, 299, 675, 1456]))
+        );
+        assert_eq!(subset_sum(&EXAMPLE, 1), Ok(None));
+        assert_eq!(subset_sum(&EXAMPLE, 0), Ok(Some(vec![])));
+        assert_eq!(subset_sum(&[], 7), Ok(None));
+    }
+
+    #[test]
+    fn test_repeated_entries() {
+        assert_eq!(subset_sum(&[5, 0, 5], 10), Ok(Some(vec![5, 5])));
+        assert_eq!(subset_sum(&[5], 10), Ok(None));
+        assert_eq!(subset_sum(&[64, 64, 1], 129), Ok(Some(vec![64, 64, 1])));
+    }
+
+    #[test]
+    fn test_max_target() {
+        assert!(subset_sum(&[MAX_TARGET], MAX_TARGET).unwrap().is_some());
+        assert_eq!(
+            subset_sum(&[1], MAX_TARGET + 1),
+            Err(Error::Other(
+                "target 4194305 is above the limit of 4194304".into()
+            ))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(data in prop::collection::vec(0..300u32, 0..12), target in 0..1000u32) {
+            let subset = subset_sum(&data, target).unwrap();
+            prop_assert_eq!(subset.is_some(), brute_force(&data, target));
+            if let Some(subset) = subset {
+                prop_assert_eq!(subset.iter().sum::<u32>(), target);
+                // The entries are picked in the order of the data
+                let mut rest = data.iter();
+                for e in subset {
+                    prop_assert!(rest.any(|&r| r == e));
+                }
+            }
+        }
+    }
+}
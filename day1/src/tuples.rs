@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common::Error;
+
+use crate::Semantics;
+
+// Every pair of values summing to target, a pair is given once in ascending
+// order however many entries give it
+pub fn all_pairs(data: &[u32], target: u32, semantics: Semantics) -> Vec<(u32, u32)> {
+    let values = frequencies(data);
+    let mut pairs = vec![];
+    value_tuples(&values, target, 2, |t| {
+        if ways(&values, t, semantics) != Some(0) {
+            pairs.push((values[t[0]].0, values[t[1]].0));
+        }
+    });
+    pairs
+}
+
+pub fn all_triples(data: &[u32], target: u32, semantics: Semantics) -> Vec<(u32, u32, u32)> {
+    let values = frequencies(data);
+    let mut triples = vec![];
+    value_tuples(&values, target, 3, |t| {
+        if ways(&values, t, semantics) != Some(0) {
+            triples.push((values[t[0]].0, values[t[1]].0, values[t[2]].0));
+        }
+    });
+    triples
+}
+
+// Number of pairs of entries summing to target, counted from the frequency
+// of each value without going through the pairs of entries. An error when
+// the count doesn't fit in a u64
+pub fn count_pairs(data: &[u32], target: u32, semantics: Semantics) -> common::Result<u64> {
+    count(data, target, 2, semantics)
+}
+
+pub fn count_triples(data: &[u32], target: u32, semantics: Semantics) -> common::Result<u64> {
+    count(data, target, 3, semantics)
+}
+
+fn count(data: &[u32], target: u32, size: usize, semantics: Semantics) -> common::Result<u64> {
+    let values = frequencies(data);
+    let mut total = Some(0u64);
+    value_tuples(&values, target, size, |t| {
+        total = total
+            .zip(ways(&values, t, semantics))
+            .and_then(|(total, ways)| total.checked_add(ways));
+    });
+    total.ok_or_else(|| Error::Other(format!("more than {} tuples", u64::MAX)))
+}
+
+// Distinct values in ascending order with their number of entries
+fn frequencies(data: &[u32]) -> Vec<(u32, u64)> {
+    let mut frequencies: BTreeMap<u32, u64> = BTreeMap::new();
+    for &e in data {
+        *frequencies.entry(e).or_insert(0) += 1;
+    }
+    frequencies.into_iter().collect()
+}
+
+// Call f with the indices in values of every ascending tuple of 2 or 3
+// values summing to target, a value can be repeated. The last two values
+// are found with two pointers from the first one
+fn value_tuples(values: &[(u32, u64)], target: u32, size: usize, mut f: impl FnMut(&[usize])) {
+    let target = u64::from(target);
+    if size == 2 {
+        return two_pointers(values, 0, target, |i, j| f(&[i, j]));
+    }
+    for (i, &(v, _)) in values.iter().enumerate() {
+        let v = u64::from(v);
+        if v > target {
+            break;
+        }
+        two_pointers(values, i, target - v, |j, l| f(&[i, j, l]));
+    }
+}
+
+// Pairs of indices from <= i <= j with values summing to target
+fn two_pointers(values: &[(u32, u64)], from: usize, target: u64, mut f: impl FnMut(usize, usize)) {
+    let (mut i, mut j) = match values.len().checked_sub(1) {
+        Some(last) => (from, last),
+        None => return,
+    };
+    while i <= j {
+        match (u64::from(values[i].0) + u64::from(values[j].0)).cmp(&target) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater if j == 0 => return,
+            std::cmp::Ordering::Greater => j -= 1,
+            std::cmp::Ordering::Equal => {
+                f(i, j);
+                i += 1;
+                if j == 0 {
+                    return;
+                }
+                j -= 1;
+            }
+        }
+    }
+}
+
+// Ways to pick the entries of an ascending tuple of indices in values, an
+// index repeated r times is r of the entries with this value. None when it
+// doesn't fit in a u64
+fn ways(values: &[(u32, u64)], tuple: &[usize], semantics: Semantics) -> Option<u64> {
+    let mut ways: u64 = 1;
+    let mut i = 0;
+    while i < tuple.len() {
+        let r = tuple[i..].iter().take_while(|&&v| v == tuple[i]).count();
+        ways = ways.checked_mul(choose(values[tuple[i]].1, r as u64, semantics)?)?;
+        i += r;
+    }
+    Some(ways)
+}
+
+// r among f entries, with repetition for a multiset
+fn choose(f: u64, r: u64, semantics: Semantics) -> Option<u64> {
+    let n = match semantics {
+        Semantics::Distinct => f,
+        Semantics::Multiset => f.checked_add(r - 1)?,
+    };
+    if n < r {
+        return Some(0);
+    }
+    // Every partial product is a binomial coefficient, so the division is
+    // exact, and it's done on 128 bits before checking it fits
+    (0..r).try_fold(1u64, |c, i| {
+        u64::try_from(u128::from(c) * u128::from(n - i) / u128::from(i + 1)).ok()
+    })
+}
+
+#[cfg(test)]
+mod test_tuples {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::fixture::{sums, EXAMPLE};
+
+    fn brute_force(data: &[u32], target: u32, size: usize, semantics: Semantics) -> u64 {
+        sums(data, size, semantics)
+            .into_iter()
+            .filter(|&s| s == u64::from(target))
+            .count() as u64
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            all_pairs(&EXAMPLE, 2020, Semantics::Distinct),
+            vec![(299, 1721)]
+        );
+        assert_eq!(
+            all_triples(&EXAMPLE, 2020, Semantics::Distinct),
+            vec![(366, 675, 979)]
+        );
+        assert_eq!(count_pairs(&EXAMPLE, 2020, Semantics::Distinct), Ok(1));
+        assert_eq!(count_triples(&EXAMPLE, 2020, Semantics::Distinct), Ok(1));
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let data = [5, 5, 5, 5, 0, 10];
+        assert_eq!(
+            all_pairs(&data, 10, Semantics::Distinct),
+            vec![(0, 10), (5, 5)]
+        );
+        assert_eq!(count_pairs(&data, 10, Semantics::Distinct), Ok(1 + 6));
+        assert_eq!(count_pairs(&data, 10, Semantics::Multiset), Ok(1 + 10));
+        assert_eq!(
+            all_triples(&data, 15, Semantics::Distinct),
+            vec![(0, 5, 10), (5, 5, 5)]
+        );
+        assert_eq!(count_triples(&data, 15, Semantics::Distinct), Ok(4 + 4));
+        assert_eq!(all_triples(&[5], 15, Semantics::Multiset), vec![(5, 5, 5)]);
+        assert_eq!(all_triples(&[5], 15, Semantics::Distinct), vec![]);
+        assert_eq!(count_pairs(&[], 0, Semantics::Multiset), Ok(0));
+        assert_eq!(count_pairs(&[0], 0, Semantics::Multiset), Ok(1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(choose(u64::MAX, 2, Semantics::Distinct), None);
+        assert_eq!(choose(u64::MAX, 1, Semantics::Multiset), Some(u64::MAX));
+        assert_eq!(choose(u64::MAX, 2, Semantics::Multiset), None);
+        assert_eq!(
+            choose(1 << 32, 2, Semantics::Distinct),
+            Some((1 << 31) * ((1 << 32) - 1))
+        );
+        // C(5000000, 3) is above u64::MAX
+        let zeros = vec![0; 5_000_000];
+        assert!(count_triples(&zeros, 0, Semantics::Distinct).is_err());
+        assert_eq!(
+            count_pairs(&zeros, 0, Semantics::Distinct),
+            Ok(5_000_000 * 4_999_999 / 2)
+        );
+        assert_eq!(all_triples(&zeros, 0, Semantics::Distinct), vec![(0, 0, 0)]);
+    }
+
+    proptest! {
+        #[test]
+        fn counts_match_brute_force(
+            data in prop::collection::vec(0..20u32, 0..15),
+            target in 0..50u32,
+            multiset in any::<bool>(),
+        ) {
+            let semantics = if multiset { Semantics::Multiset } else { Semantics::Distinct };
+            prop_assert_eq!(count_pairs(&data, target, semantics).unwrap(), brute_force(&data, target, 2, semantics));
+            prop_assert_eq!(count_triples(&data, target, semantics).unwrap(), brute_force(&data, target, 3, semantics));
+            for (a, b, c) in all_triples(&data, target, semantics) {
+                prop_assert!(a <= b && b <= c && a + b + c == target);
+            }
+        }
+    }
+}
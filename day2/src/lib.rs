@@ -0,0 +1,131 @@
+use std::convert::TryFrom;
+
+use common::Solution;
+
+pub mod policy;
+pub mod report;
+
+use policy::{Policy, Position, Quantity};
+
+pub struct Day2;
+
+pub struct PasswordConstrains {
+    letter: char,
+    range: (usize, usize),
+}
+
+impl TryFrom<&str> for PasswordConstrains {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let splited_value: Vec<&str> = value
+            .split(|c: char| c == '-' || c.is_whitespace())
+            .collect();
+        if splited_value.len() != 3 {
+            return Err(format!("invalid policy {:?}", value));
+        };
+        let letter = match splited_value[2].chars().collect::<Vec<_>>()[..] {
+            [letter] => letter,
+            _ => return Err(format!("invalid letter {:?}", splited_value[2])),
+        };
+        let range = match (
+            splited_value[0].parse::<usize>(),
+            splited_value[1].parse::<usize>(),
+        ) {
+            (Ok(x), Ok(y)) => (x, y),
+            _ => return Err(format!("invalid range {:?}", value)),
+        };
+        Ok(PasswordConstrains { letter, range })
+    }
+}
+
+impl PasswordConstrains {
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    pub fn range(&self) -> (usize, usize) {
+        self.range
+    }
+}
+
+impl Solution for Day2 {
+    type Input = Vec<(PasswordConstrains, String)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        common::parse_lines(input, parse_line)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+// Constraints and password of a "<policy>: <password>" line
+pub fn parse_line(line: &str) -> Result<(PasswordConstrains, String), String> {
+    match line.split(": ").collect::<Vec<_>>()[..] {
+        [policy, password] => Ok((PasswordConstrains::try_from(policy)?, password.to_string())),
+        _ => Err("expected \"<policy>: <password>\"".to_string()),
+    }
+}
+
+pub fn part1(data: &[(PasswordConstrains, String)]) -> usize {
+    data.iter()
+        .filter(|(c, password)| Quantity.validate(c, password))
+        .count()
+}
+
+pub fn part2(data: &[(PasswordConstrains, String)]) -> usize {
+    data.iter()
+        .filter(|(c, password)| Position.validate(c, password))
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tryfrom() {
+        assert!(PasswordConstrains::try_from("8-11 l").is_ok());
+        assert!(PasswordConstrains::try_from("8-11").is_err());
+        assert!(PasswordConstrains::try_from("11-8 l").is_ok());
+        assert!(PasswordConstrains::try_from("1-x l").is_err());
+        assert!(PasswordConstrains::try_from("1-3 ab").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day2::parse("1-3 a: abcde\n1-3 b: cdefg").unwrap().len(), 2);
+        assert_eq!(
+            Day2::parse("1-3 a: abcde\n1-3 b cdefg").err(),
+            Some(common::Error::parse(
+                2,
+                "\"1-3 b cdefg\": expected \"<policy>: <password>\""
+            ))
+        );
+        assert!(!Position.validate(&PasswordConstrains::try_from("0-1 a").unwrap(), "a"));
+    }
+
+    #[test]
+    fn test_validate_quantity() {
+        assert!(Quantity.validate(&PasswordConstrains::try_from("1-3 a").unwrap(), "abcde"));
+        assert!(!Quantity.validate(&PasswordConstrains::try_from("1-3 b").unwrap(), "cdefg"));
+        assert!(Quantity.validate(&PasswordConstrains::try_from("2-9 c").unwrap(), "ccccccccc"));
+    }
+
+    #[test]
+    fn test_validate_position() {
+        assert!(Position.validate(&PasswordConstrains::try_from("1-3 a").unwrap(), "abcde"));
+        assert!(!Position.validate(&PasswordConstrains::try_from("1-3 b").unwrap(), "cdefg"));
+        assert!(!Position.validate(&PasswordConstrains::try_from("2-9 c").unwrap(), "ccccccccc"));
+        assert!(Position.validate(&PasswordConstrains::try_from("3-1 b").unwrap(), "bba"));
+        assert!(!Position.validate(&PasswordConstrains::try_from("3-1 b").unwrap(), "bbb"));
+    }
+}
@@ -1,122 +1,61 @@
-use std::convert::TryFrom;
+use std::fs;
 
-enum PasswordPolice {
-    Quantity,
-    Position,
-}
-
-struct PasswordConstrains {
-    letter: char,
-    range: (usize, usize),
-}
+use day2::policy::Registry;
+use day2::report::Report;
 
-impl TryFrom<&str> for PasswordConstrains {
-    type Error = ();
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let splited_value: Vec<&str> = value
-            .split(|c: char| c == '-' || c.is_whitespace())
-            .collect();
-        if splited_value.len() != 3 {
-            return Err(());
-        };
-        let letter = splited_value[2].chars().next().ok_or(())?;
-        let range = match (
-            splited_value[0].parse::<usize>(),
-            splited_value[1].parse::<usize>(),
-        ) {
-            (Ok(x), Ok(y)) => (x, y),
-            _ => return Err(()),
-        };
-        Ok(PasswordConstrains { letter, range })
+fn main() {
+    // Usage: day2 [path], or day2 --report <policy> [--format text|json]
+    // [path] to list the rejected lines with the reason
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("--report") {
+        return common::main::<day2::Day2>(2);
     }
-}
-
-impl PasswordConstrains {
-    fn validate(&self, police: PasswordPolice, password: &str) -> bool {
-        match police {
-            PasswordPolice::Quantity => {
-                let quantity = password.chars().filter(|e| e.eq(&self.letter)).count();
-                match quantity {
-                    x if x < self.range.0 => false,
-                    x if x > self.range.1 => false,
-                    _ => true,
-                }
-            }
-            PasswordPolice::Position => matches!((
-                password.chars().nth(self.range.0 - 1),
-                password.chars().nth(self.range.1 - 1),
-            ), (Some(x), Some(y)) if (x == self.letter) ^ (y == self.letter)),
-        }
+    if let Err(e) = report(&args[1..]) {
+        eprintln!("{}", e);
+        std::process::exit(e.exit_code());
     }
 }
 
-fn part1(data: &str) {
-    let count = data
-        .lines()
-        .map(|f| -> bool {
-            let args: Vec<&str> = f.split(": ").collect();
-            PasswordConstrains::try_from(args[0])
-                .unwrap()
-                .validate(PasswordPolice::Quantity, args[1])
-        })
-        .filter(|r| *r)
-        .count();
-    dbg!(count);
-}
-
-fn part2(data: &str) {
-    let count = data
-        .lines()
-        .map(|f| -> bool {
-            let args: Vec<&str> = f.split(": ").collect();
-            PasswordConstrains::try_from(args[0])
-                .unwrap()
-                .validate(PasswordPolice::Position, args[1])
-        })
-        .filter(|r| *r)
-        .count();
-    dbg!(count);
-}
-
-fn main() {
-    let input = include_str!("../../input/d2");
-    part1(input);
-    part2(input);
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_tryfrom() {
-        assert!(PasswordConstrains::try_from("8-11 l").is_ok());
-    }
-
-    #[test]
-    fn test_validate_quantity() {
-        assert!(PasswordConstrains::try_from("1-3 a")
-            .unwrap()
-            .validate(PasswordPolice::Quantity, "abcde"));
-        assert!(!PasswordConstrains::try_from("1-3 b")
-            .unwrap()
-            .validate(PasswordPolice::Quantity, "cdefg"));
-        assert!(PasswordConstrains::try_from("2-9 c")
-            .unwrap()
-            .validate(PasswordPolice::Quantity, "ccccccccc"));
+fn report(args: &[String]) -> common::Result<()> {
+    let registry = Registry::default();
+    let (name, mut rest) = match args {
+        [name, rest @ ..] => (name, rest),
+        [] => return Err("missing policy after --report".into()),
+    };
+    let policy = registry.get(name).ok_or_else(|| {
+        let names: Vec<&str> = registry.names().collect();
+        format!(
+            "unknown policy {}, expected one of {}",
+            name,
+            names.join(", ")
+        )
+    })?;
+    let mut json = false;
+    if let [flag, format, tail @ ..] = rest {
+        if flag == "--format" {
+            json = match format.as_str() {
+                "json" => true,
+                "text" => false,
+                _ => return Err(format!("unknown format {}, expected json or text", format).into()),
+            };
+            rest = tail;
+        }
     }
-
-    #[test]
-    fn test_validate_position() {
-        assert!(PasswordConstrains::try_from("1-3 a")
-            .unwrap()
-            .validate(PasswordPolice::Position, "abcde"));
-        assert!(!PasswordConstrains::try_from("1-3 b")
-            .unwrap()
-            .validate(PasswordPolice::Position, "cdefg"));
-        assert!(!PasswordConstrains::try_from("2-9 c")
-            .unwrap()
-            .validate(PasswordPolice::Position, "ccccccccc"));
+    let path = match rest {
+        [path] => path.clone(),
+        [] => concat!(env!("CARGO_MANIFEST_DIR"), "/../input/d2").to_string(),
+        _ => return Err(format!("unexpected argument {}", rest[1]).into()),
+    };
+    let input =
+        fs::read_to_string(&path).map_err(|e| common::Error::Io(format!("{}: {}", path, e)))?;
+    let report = Report::new(&input, policy);
+    let lines = if json {
+        report.json_lines()
+    } else {
+        report.table()
+    };
+    for line in lines {
+        println!("{}", line);
     }
+    Ok(())
 }
@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+
+use crate::PasswordConstrains;
+
+/// Rule a password must follow, given the constraints written before it on
+/// its line. Implement it to add a policy without changing this crate
+pub trait Policy {
+    // Why the password breaks the policy
+    fn check(&self, constrains: &PasswordConstrains, password: &str) -> Result<(), String>;
+
+    fn validate(&self, constrains: &PasswordConstrains, password: &str) -> bool {
+        self.check(constrains, password).is_ok()
+    }
+}
+
+/// The letter appears a number of times within the range
+pub struct Quantity;
+
+impl Policy for Quantity {
+    fn check(&self, constrains: &PasswordConstrains, password: &str) -> Result<(), String> {
+        let (min, max) = constrains.range();
+        let letter = constrains.letter();
+        // A descending range contains no quantity
+        if min > max {
+            return Err(format!(
+                "range {}-{} is descending, no quantity of {:?} is within it",
+                min, max, letter
+            ));
+        }
+        let quantity = password.chars().filter(|&c| c == letter).count();
+        if min <= quantity && quantity <= max {
+            return Ok(());
+        }
+        Err(format!(
+            "letter {:?} appears {} {}, expected {}–{}",
+            letter,
+            quantity,
+            if quantity == 1 { "time" } else { "times" },
+            min,
+            max
+        ))
+    }
+}
+
+/// The letter is at exactly one of the two positions of the range
+pub struct Position;
+
+impl Policy for Position {
+    fn check(&self, constrains: &PasswordConstrains, password: &str) -> Result<(), String> {
+        let (first, second) = constrains.range();
+        let letter = constrains.letter();
+        // Positions start at 1, a position 0 is never valid
+        let at = |p: usize| match p.checked_sub(1).map(|i| password.chars().nth(i)) {
+            Some(Some(c)) => Ok(c),
+            Some(None) => Err(format!("position {} is past the end of the password", p)),
+            None => Err("position 0 is not valid, positions start at 1".to_string()),
+        };
+        match (at(first)? == letter, at(second)? == letter) {
+            (true, true) => Err(format!(
+                "positions {} and {} both contain {:?}",
+                first, second, letter
+            )),
+            (false, false) => Err(format!(
+                "neither position {} nor {} contains {:?}",
+                first, second, letter
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// At least this number of characters
+pub struct MinLength(pub usize);
+
+impl Policy for MinLength {
+    fn check(&self, _: &PasswordConstrains, password: &str) -> Result<(), String> {
+        let length = password.chars().count();
+        if length >= self.0 {
+            return Ok(());
+        }
+        Err(format!(
+            "length {} is below the minimum of {}",
+            length, self.0
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    // Any other character
+    Symbol,
+}
+
+impl CharClass {
+    pub fn name(self) -> &'static str {
+        match self {
+            CharClass::Lower => "lowercase",
+            CharClass::Upper => "uppercase",
+            CharClass::Digit => "digit",
+            CharClass::Symbol => "symbol",
+        }
+    }
+
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            CharClass::Lower => c.is_lowercase(),
+            CharClass::Upper => c.is_uppercase(),
+            CharClass::Digit => c.is_numeric(),
+            CharClass::Symbol => !c.is_alphanumeric(),
+        }
+    }
+}
+
+/// At least one character of each class
+pub struct RequiredClasses(pub Vec<CharClass>);
+
+impl Policy for RequiredClasses {
+    fn check(&self, _: &PasswordConstrains, password: &str) -> Result<(), String> {
+        match self
+            .0
+            .iter()
+            .find(|class| !password.chars().any(|c| class.contains(c)))
+        {
+            Some(class) => Err(format!("no {} character", class.name())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// No character repeated more than this number of times in a row
+pub struct NoRepeatedRuns(pub usize);
+
+impl Policy for NoRepeatedRuns {
+    fn check(&self, _: &PasswordConstrains, password: &str) -> Result<(), String> {
+        let mut run = 0;
+        let mut last = None;
+        for c in password.chars() {
+            run = if last == Some(c) { run + 1 } else { 1 };
+            last = Some(c);
+            if run > self.0 {
+                return Err(format!(
+                    "{:?} is repeated more than {} times in a row",
+                    c, self.0
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// None of the substrings, ignoring the case
+pub struct ForbiddenSubstrings(pub Vec<String>);
+
+impl Policy for ForbiddenSubstrings {
+    fn check(&self, _: &PasswordConstrains, password: &str) -> Result<(), String> {
+        let password = password.to_lowercase();
+        match self
+            .0
+            .iter()
+            .find(|forbidden| password.contains(&forbidden.to_lowercase()))
+        {
+            Some(forbidden) => Err(format!("contains the forbidden {:?}", forbidden)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Policies by name. The quantity and position policies of the puzzle and
+/// the other built-in policies, with common parameters, are registered by
+/// default
+pub struct Registry {
+    policies: BTreeMap<String, Box<dyn Policy>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry::empty();
+        registry.register("quantity", Quantity);
+        registry.register("position", Position);
+        registry.register("min-length", MinLength(12));
+        registry.register(
+            "required-classes",
+            RequiredClasses(vec![
+                CharClass::Lower,
+                CharClass::Upper,
+                CharClass::Digit,
+                CharClass::Symbol,
+            ]),
+        );
+        registry.register("no-repeated-runs", NoRepeatedRuns(2));
+        registry.register(
+            "forbidden-substrings",
+            ForbiddenSubstrings(vec!["password".into(), "1234".into(), "qwerty".into()]),
+        );
+        registry
+    }
+}
+
+impl Registry {
+    pub fn empty() -> Self {
+        Registry {
+            policies: BTreeMap::new(),
+        }
+    }
+
+    // Add a policy, replacing the one already registered with this name
+    pub fn register(&mut self, name: &str, policy: impl Policy + 'static) {
+        self.policies.insert(name.to_string(), Box::new(policy));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Policy> {
+        self.policies.get(name).map(|p| p.as_ref())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.policies.keys().map(|name| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test_policy {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn valid(policy: &dyn Policy, password: &str) -> bool {
+        policy.validate(&PasswordConstrains::try_from("1-3 a").unwrap(), password)
+    }
+
+    #[test]
+    fn test_min_length() {
+        assert!(valid(&MinLength(3), "abc"));
+        assert!(valid(&MinLength(3), "été"));
+        assert!(!valid(&MinLength(3), "ab"));
+    }
+
+    #[test]
+    fn test_required_classes() {
+        let policy = RequiredClasses(vec![CharClass::Lower, CharClass::Digit, CharClass::Symbol]);
+        assert!(valid(&policy, "ab1!"));
+        assert!(!valid(&policy, "ab1"));
+        assert!(!valid(&policy, "AB1!"));
+        assert!(valid(&RequiredClasses(vec![]), ""));
+    }
+
+    #[test]
+    fn test_no_repeated_runs() {
+        assert!(valid(&NoRepeatedRuns(2), "aabbaa"));
+        assert!(!valid(&NoRepeatedRuns(2), "abbbc"));
+        assert!(valid(&NoRepeatedRuns(1), ""));
+    }
+
+    #[test]
+    fn test_forbidden_substrings() {
+        let policy = ForbiddenSubstrings(vec!["password".into(), "1234".into()]);
+        assert!(valid(&policy, "correct horse"));
+        assert!(!valid(&policy, "MyPassWord!"));
+        assert!(!valid(&policy, "a12345"));
+    }
+
+    struct Even;
+
+    impl Policy for Even {
+        fn check(&self, _: &PasswordConstrains, password: &str) -> Result<(), String> {
+            if password.len().is_multiple_of(2) {
+                Ok(())
+            } else {
+                Err("odd length".into())
+            }
+        }
+    }
+
+    #[test]
+    fn test_reasons() {
+        let check = |policy: &dyn Policy, constrains: &str, password: &str| {
+            policy
+                .check(&PasswordConstrains::try_from(constrains).unwrap(), password)
+                .unwrap_err()
+        };
+        assert_eq!(
+            check(&Quantity, "1-3 b", "cdefg"),
+            "letter 'b' appears 0 times, expected 1–3"
+        );
+        assert_eq!(
+            check(&Quantity, "2-9 c", "c"),
+            "letter 'c' appears 1 time, expected 2–9"
+        );
+        assert_eq!(
+            check(&Quantity, "3-1 b", "bb"),
+            "range 3-1 is descending, no quantity of 'b' is within it"
+        );
+        assert_eq!(
+            check(&Position, "1-3 c", "cdcfg"),
+            "positions 1 and 3 both contain 'c'"
+        );
+        assert_eq!(
+            check(&Position, "1-3 b", "cdefg"),
+            "neither position 1 nor 3 contains 'b'"
+        );
+        assert_eq!(
+            check(&Position, "1-9 a", "abc"),
+            "position 9 is past the end of the password"
+        );
+        assert_eq!(
+            check(&Position, "0-1 a", "a"),
+            "position 0 is not valid, positions start at 1"
+        );
+        assert_eq!(
+            check(&MinLength(3), "1-3 a", "ab"),
+            "length 2 is below the minimum of 3"
+        );
+        assert_eq!(
+            check(
+                &RequiredClasses(vec![CharClass::Lower, CharClass::Digit]),
+                "1-3 a",
+                "ab"
+            ),
+            "no digit character"
+        );
+        assert_eq!(
+            check(&NoRepeatedRuns(2), "1-3 a", "abbbc"),
+            "'b' is repeated more than 2 times in a row"
+        );
+        assert_eq!(
+            check(
+                &ForbiddenSubstrings(vec!["Password".into()]),
+                "1-3 a",
+                "mypassword"
+            ),
+            "contains the forbidden \"Password\""
+        );
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::default();
+        assert!(!valid(registry.get("min-length").unwrap(), "abcdefghijk"));
+        assert!(valid(registry.get("required-classes").unwrap(), "aB3$"));
+        assert!(!valid(registry.get("required-classes").unwrap(), "aB3"));
+        assert!(!valid(registry.get("no-repeated-runs").unwrap(), "aaa"));
+        assert!(!valid(
+            registry.get("forbidden-substrings").unwrap(),
+            "Qwerty!"
+        ));
+        registry.register("even", Even);
+        registry.register("min-length", MinLength(2));
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec![
+                "even",
+                "forbidden-substrings",
+                "min-length",
+                "no-repeated-runs",
+                "position",
+                "quantity",
+                "required-classes"
+            ]
+        );
+        assert!(valid(registry.get("min-length").unwrap(), "ab"));
+        assert!(valid(registry.get("quantity").unwrap(), "abcde"));
+        assert!(!valid(registry.get("even").unwrap(), "abcde"));
+        assert!(registry.get("unknown").is_none());
+        assert_eq!(Registry::empty().names().count(), 0);
+    }
+}
@@ -0,0 +1,192 @@
+use serde_json::json;
+
+use crate::parse_line;
+use crate::policy::Policy;
+
+/// Why a line of the input was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    // The password breaks the policy
+    Invalid(String),
+    // The line isn't "<policy>: <password>"
+    Malformed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    // Line number in the input, starting at 1
+    pub line: usize,
+    pub text: String,
+    pub problem: Problem,
+}
+
+/// Every line of the input checked against a policy, the rejected lines are
+/// kept in the order of the input
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub valid: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    // A malformed line is reported like a rejected password instead of
+    // stopping the report
+    pub fn new(input: &str, policy: &dyn Policy) -> Self {
+        let mut report = Report::default();
+        for (i, text) in input.lines().enumerate() {
+            let problem = match parse_line(text) {
+                Ok((constrains, password)) => match policy.check(&constrains, &password) {
+                    Ok(()) => {
+                        report.valid += 1;
+                        continue;
+                    }
+                    Err(reason) => Problem::Invalid(reason),
+                },
+                Err(message) => Problem::Malformed(message),
+            };
+            report.findings.push(Finding {
+                line: i + 1,
+                text: text.to_string(),
+                problem,
+            });
+        }
+        report
+    }
+
+    pub fn invalid(&self) -> usize {
+        self.count(|p| matches!(p, Problem::Invalid(_)))
+    }
+
+    pub fn malformed(&self) -> usize {
+        self.count(|p| matches!(p, Problem::Malformed(_)))
+    }
+
+    fn count(&self, is: impl Fn(&Problem) -> bool) -> usize {
+        self.findings.iter().filter(|f| is(&f.problem)).count()
+    }
+
+    // A row for each rejected line and the counts
+    pub fn table(&self) -> Vec<String> {
+        let width = self
+            .findings
+            .iter()
+            .map(|f| f.text.chars().count())
+            .chain(Some("input".len()))
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![format!(
+            "{:<6} {:<width$}  reason",
+            "line",
+            "input",
+            width = width
+        )];
+        for finding in &self.findings {
+            let reason = match &finding.problem {
+                Problem::Invalid(reason) => reason.clone(),
+                Problem::Malformed(message) => format!("malformed: {}", message),
+            };
+            lines.push(format!(
+                "{:<6} {:<width$}  {}",
+                finding.line,
+                finding.text,
+                reason,
+                width = width
+            ));
+        }
+        lines.push(self.summary());
+        lines
+    }
+
+    // A {"line", "input", "invalid" or "malformed"} line for each rejected
+    // line, then the {"valid", "invalid", "malformed"} counts
+    pub fn json_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let mut line = json!({ "line": finding.line, "input": finding.text });
+                match &finding.problem {
+                    Problem::Invalid(reason) => line["invalid"] = reason.as_str().into(),
+                    Problem::Malformed(message) => line["malformed"] = message.as_str().into(),
+                }
+                line.to_string()
+            })
+            .collect();
+        lines.push(
+            json!({
+                "valid": self.valid,
+                "invalid": self.invalid(),
+                "malformed": self.malformed(),
+            })
+            .to_string(),
+        );
+        lines
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} valid, {} invalid, {} malformed",
+            self.valid,
+            self.invalid(),
+            self.malformed()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_report {
+    use super::*;
+    use crate::policy::{Position, Quantity};
+
+    const INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 b cdefg\n";
+
+    #[test]
+    fn test_report() {
+        let report = Report::new(INPUT, &Quantity);
+        assert_eq!(report.valid, 2);
+        assert_eq!(
+            report.findings,
+            vec![
+                Finding {
+                    line: 2,
+                    text: "1-3 b: cdefg".into(),
+                    problem: Problem::Invalid("letter 'b' appears 0 times, expected 1–3".into()),
+                },
+                Finding {
+                    line: 4,
+                    text: "1-3 b cdefg".into(),
+                    problem: Problem::Malformed("expected \"<policy>: <password>\"".into()),
+                },
+            ]
+        );
+        assert_eq!((report.invalid(), report.malformed()), (1, 1));
+        assert_eq!(Report::new(INPUT, &Position).invalid(), 2);
+        assert_eq!(Report::new("", &Position), Report::default());
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            Report::new(INPUT, &Position).table(),
+            vec![
+                "line   input             reason",
+                "2      1-3 b: cdefg      neither position 1 nor 3 contains 'b'",
+                "3      2-9 c: ccccccccc  positions 2 and 9 both contain 'c'",
+                "4      1-3 b cdefg       malformed: expected \"<policy>: <password>\"",
+                "1 valid, 2 invalid, 1 malformed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(
+            Report::new(INPUT, &Quantity).json_lines(),
+            vec![
+                r#"{"input":"1-3 b: cdefg","invalid":"letter 'b' appears 0 times, expected 1–3","line":2}"#,
+                r#"{"input":"1-3 b cdefg","line":4,"malformed":"expected \"<policy>: <password>\""}"#,
+                r#"{"invalid":1,"malformed":1,"valid":2}"#,
+            ]
+        );
+    }
+}
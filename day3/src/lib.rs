@@ -0,0 +1,102 @@
+use common::{Error, Solution};
+
+pub struct Day3;
+
+#[derive(PartialEq, Debug)]
+pub enum MapTile {
+    Floor,
+    Tree,
+}
+
+fn parse_map(data: &str) -> common::Result<Vec<Vec<MapTile>>> {
+    let map = common::parse_lines(data, |l| {
+        l.chars()
+            .map(|e| match e {
+                '#' => Ok(MapTile::Tree),
+                '.' => Ok(MapTile::Floor),
+                _ => Err(format!("invalid tile {:?}", e)),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    // Every row repeats to the right, so they must share a non zero width
+    let width = map.first().map_or(0, Vec::len);
+    if width == 0 {
+        return Err(Error::parse(1, "empty row"));
+    }
+    match map.iter().position(|row| row.len() != width) {
+        Some(i) => Err(Error::parse(
+            i + 1,
+            format!("expected {} tiles, found {}", width, map[i].len()),
+        )),
+        None => Ok(map),
+    }
+}
+
+fn travese_tree(map: &[Vec<MapTile>], (right, down): (usize, usize)) -> usize {
+    let size = map[0].len();
+    map.iter()
+        .step_by(down)
+        .enumerate()
+        .fold(0, |tot, (pos, vec)| {
+            if vec[(pos * right) % size] == MapTile::Tree {
+                tot + 1
+            } else {
+                tot
+            }
+        })
+}
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<MapTile>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        parse_map(input)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub fn part1(map: &[Vec<MapTile>]) -> usize {
+    travese_tree(map, (1, 2))
+}
+
+pub fn part2(map: &[Vec<MapTile>]) -> usize {
+    let slopes: Vec<(usize, usize)> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    slopes.iter().map(|e| travese_tree(map, *e)).product()
+}
+
+#[cfg(test)]
+mod test_tree {
+    use super::*;
+
+    #[test]
+    fn test_travese() {
+        let map = parse_map("..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#").unwrap();
+        assert_eq!(2, travese_tree(&map, (1, 1)));
+        assert_eq!(7, travese_tree(&map, (3, 1)));
+        assert_eq!(3, travese_tree(&map, (5, 1)));
+        assert_eq!(4, travese_tree(&map, (7, 1)));
+        assert_eq!(2, travese_tree(&map, (1, 2)));
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(parse_map(""), Err(Error::parse(1, "empty row")));
+        assert_eq!(
+            parse_map("..#\n.#"),
+            Err(Error::parse(2, "expected 3 tiles, found 2"))
+        );
+        assert_eq!(
+            parse_map("..#\n.x."),
+            Err(Error::parse(2, "\".x.\": invalid tile 'x'"))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use common::{Error, Solution};
+
+pub struct Day4;
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+#[derive(PartialEq, Debug, Default)]
+pub struct Document {
+    fields: HashMap<String, String>,
+}
+
+impl TryFrom<&str> for Document {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut document = Document::default();
+        for line in value.lines() {
+            document.add_fields(line)?;
+        }
+        Ok(document)
+    }
+}
+
+impl Document {
+    // Add the key:value fields of a line of the document
+    fn add_fields(&mut self, line: &str) -> Result<(), String> {
+        for field in line.split_whitespace() {
+            match field.split_once(':') {
+                Some((key, value)) if FIELDS.contains(&key) => {
+                    self.fields.insert(key.into(), value.into());
+                }
+                _ => return Err(format!("invalid field {:?}", field)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Solution for Day4 {
+    type Input = Vec<Document>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        // Documents are separated by blank lines
+        let mut documents = vec![];
+        let mut document: Option<Document> = None;
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                documents.extend(document.take());
+                continue;
+            }
+            document
+                .get_or_insert_with(Document::default)
+                .add_fields(line)
+                .map_err(|e| Error::parse(i + 1, e))?;
+        }
+        documents.extend(document);
+        Ok(documents)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub fn part1(documents: &[Document]) -> usize {
+    documents.iter().filter(|d| validate_fields(d)).count()
+}
+
+pub fn part2(documents: &[Document]) -> usize {
+    documents
+        .iter()
+        .filter(|d| validate_fields(d))
+        .filter(|d| validate(d))
+        .count()
+}
+
+fn validate_fields(document: &Document) -> bool {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    required.iter().all(|s| document.fields.contains_key(*s))
+}
+
+fn validate(d: &Document) -> bool {
+    d.fields.iter().all(|(key, value)| {
+        let value = value.as_str();
+        match key.as_str() {
+            "byr" => (1920..=2002).contains(&value.parse().unwrap_or(0)),
+            "iyr" => (2010..=2020).contains(&value.parse().unwrap_or(0)),
+            "eyr" => (2020..=2030).contains(&value.parse().unwrap_or(0)),
+            "hgt" => match (value.strip_suffix("in"), value.strip_suffix("cm")) {
+                (Some(h), _) => (59..=76).contains(&h.parse().unwrap_or(0)),
+                (_, Some(h)) => (150..=193).contains(&h.parse().unwrap_or(0)),
+                _ => false,
+            },
+            "hcl" => {
+                value.starts_with('#')
+                    && value.len() == 7
+                    && value.chars().skip(1).all(|h| h.is_ascii_hexdigit())
+            }
+            "ecl" => matches!(value, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"),
+            "pid" => value.len() == 9 && value.chars().all(|d| d.is_ascii_digit()),
+            _ => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let document = Document::try_from("pid:087499704").unwrap();
+        assert!(!validate_fields(&document));
+        assert!(validate(&document));
+        let document = Document::try_from(
+            "eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+        )
+        .unwrap();
+        assert!(validate_fields(&document));
+        assert!(!validate(&document));
+        let document = Document::try_from(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+        )
+        .unwrap();
+        assert!(validate_fields(&document));
+        assert!(validate(&document));
+        let document = Document::try_from("hgt:in").unwrap();
+        assert!(!validate(&document));
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "ecl:gry pid:860033327\nbyr:1937\n\n\nhcl:#cfa07d\n";
+        let documents = Day4::parse(input).unwrap();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].fields.len(), 3);
+        assert_eq!(
+            Day4::parse("ecl:gry\n\nhcl:#cfa07d foo:1").err(),
+            Some(Error::parse(3, "invalid field \"foo:1\""))
+        );
+        assert_eq!(
+            Document::try_from("ecl:gry pid"),
+            Err("invalid field \"pid\"".into())
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::convert::TryFrom;
+
+use common::{no_solution, Solution};
+
+pub struct Day5;
+
+#[derive(Debug, PartialEq)]
+pub struct Seat {
+    row: usize,
+    column: usize,
+}
+
+impl TryFrom<&str> for Seat {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() != 10 {
+            return Err(format!("invalid length {}", value.len()));
+        }
+        // The seat id is the binary number spelled by the characters
+        value
+            .chars()
+            .enumerate()
+            .try_fold(0, |id, (i, c)| match (i < 7, c) {
+                (true, 'F') | (false, 'L') => Ok(id << 1),
+                (true, 'B') | (false, 'R') => Ok(id << 1 | 1),
+                _ => Err(format!("invalid character {:?} at column {}", c, i + 1)),
+            })
+            .map(Seat::from)
+    }
+}
+
+impl From<usize> for Seat {
+    fn from(v: usize) -> Self {
+        Seat {
+            row: v >> 3,
+            column: v & 0b111,
+        }
+    }
+}
+
+impl Seat {
+    fn get_id(&self) -> usize {
+        self.row * 8 + self.column
+    }
+}
+
+impl Solution for Day5 {
+    type Input = Vec<Seat>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        common::parse_lines(input, |line| Seat::try_from(line))
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        part1(input).map_or_else(no_solution, Ok)
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        part2(input).map_or_else(no_solution, Ok)
+    }
+}
+
+pub fn part1(seats: &[Seat]) -> Option<usize> {
+    seats.iter().map(|s| s.get_id()).max()
+}
+
+pub fn part2(seats: &[Seat]) -> Option<usize> {
+    let mut seat_exist = seats
+        .iter()
+        .filter(|s| s.row != 0 && s.row != 127)
+        .map(|s| s.get_id())
+        .collect::<Vec<_>>();
+    seat_exist.sort_unstable();
+    let seat = seat_exist
+        .windows(2)
+        .filter(|&e| e[1] - e[0] == 2)
+        .collect::<Vec<_>>();
+    seat.first().map(|e| e[0] + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            Seat::try_from("BFFFBBFRRR").unwrap(),
+            Seat { row: 70, column: 7 }
+        );
+        assert_eq!(
+            Seat::try_from("FFFBBBFRRR").unwrap(),
+            Seat { row: 14, column: 7 }
+        );
+        assert_eq!(
+            Seat::try_from("BBFFBBFRLL").unwrap(),
+            Seat {
+                row: 102,
+                column: 4
+            }
+        );
+    }
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Seat::try_from("BFFFBBF"), Err("invalid length 7".into()));
+        assert_eq!(
+            Seat::try_from("BFFFBBFRRX"),
+            Err("invalid character 'X' at column 10".into())
+        );
+        assert_eq!(
+            Seat::try_from("BFFFBBRRRR"),
+            Err("invalid character 'R' at column 7".into())
+        );
+        assert_eq!(
+            Day5::parse("BFFFBBFRRR\nBFFFBBFRRé").err(),
+            Some(common::Error::parse(2, "\"BFFFBBFRRé\": invalid length 11"))
+        );
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Seat::from(567), Seat { row: 70, column: 7 });
+        assert_eq!(Seat::from(119), Seat { row: 14, column: 7 });
+        assert_eq!(
+            Seat::from(820),
+            Seat {
+                row: 102,
+                column: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use common::{Error, Solution};
+
+pub struct Day6;
+
+// Answers of each person of the group
+pub type Group = Vec<HashSet<char>>;
+
+impl Solution for Day6 {
+    type Input = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        // Groups are separated by blank lines, a person answers a to z
+        let mut groups = vec![];
+        let mut group = Group::new();
+        for (i, line) in input.lines().enumerate() {
+            if let Some(c) = line.chars().find(|c| !c.is_ascii_lowercase()) {
+                return Err(Error::parse(i + 1, format!("invalid answer {:?}", c)));
+            }
+            if line.is_empty() {
+                if !group.is_empty() {
+                    groups.push(std::mem::take(&mut group));
+                }
+            } else {
+                group.push(line.chars().collect());
+            }
+        }
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub fn part1(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|g| g.iter().flatten().collect::<HashSet<_>>().len())
+        .sum()
+}
+
+pub fn part2(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|g| {
+            let mut all: HashSet<_> = ('a'..='z').collect();
+            g.iter().for_each(|e| {
+                all = &all & e;
+            });
+            all.len()
+        })
+        .sum()
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use common::{no_solution, Error, Solution};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, space1},
+    combinator::{map, map_res, recognize},
+    multi::separated_list1,
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+pub struct Day7;
+
+#[derive(Debug)]
+pub struct Bag {
+    name: String,
+    insides: Vec<(u32, String)>,
+}
+
+pub type BagsRules = HashMap<String, Bag>;
+
+pub fn parse_bags(input: &str) -> common::Result<BagsRules> {
+    let bags = common::parse_lines(input, parse_bag)?;
+    let mut rules = HashMap::new();
+    for (i, bag) in bags.into_iter().enumerate() {
+        if rules.contains_key(&bag.name) {
+            return Err(Error::parse(
+                i + 1,
+                format!("duplicate rule for {}", bag.name),
+            ));
+        }
+        rules.insert(bag.name.clone(), (i + 1, bag));
+    }
+    // Every bag inside another needs its own rule, and no bag can end up
+    // inside itself or counting would never end
+    let mut done = HashMap::new();
+    for name in rules.keys() {
+        check_bag(&rules, name, &mut done)?;
+    }
+    Ok(rules
+        .into_iter()
+        .map(|(name, (_, bag))| (name, bag))
+        .collect())
+}
+
+// Depth first search of the bags inside name, done is false while the bag
+// is on the current path
+fn check_bag<'a>(
+    rules: &'a HashMap<String, (usize, Bag)>,
+    name: &'a str,
+    done: &mut HashMap<&'a str, bool>,
+) -> common::Result<()> {
+    match done.get(name) {
+        Some(true) => return Ok(()),
+        Some(false) => {
+            let message = format!("{} bags contain themselves", name);
+            return Err(Error::parse(rules[name].0, message));
+        }
+        None => {}
+    }
+    done.insert(name, false);
+    let (line, bag) = &rules[name];
+    for (_, inside) in &bag.insides {
+        if !rules.contains_key(inside) {
+            return Err(Error::parse(*line, format!("no rule for {} bags", inside)));
+        }
+        check_bag(rules, inside, done)?;
+    }
+    done.insert(name, true);
+    Ok(())
+}
+
+fn parse_bag(input: &str) -> Result<Bag, String> {
+    let result = tuple((
+        bag_name,
+        tag(" contain "),
+        alt((
+            map(tag("no other bags"), |_| Vec::new()),
+            separated_list1(
+                tag(", "),
+                map(
+                    tuple((map_res(digit1, str::parse::<u32>), space1, bag_name)),
+                    |tuple| (tuple.0, tuple.2.to_string()),
+                ),
+            ),
+        )),
+    ))(input);
+    if let Ok((_, (name, _, insides))) = result {
+        Ok(Bag {
+            name: name.into(),
+            insides,
+        })
+    } else {
+        Err("invalid rule".into())
+    }
+}
+
+fn bag_name(input: &str) -> IResult<&str, &str> {
+    terminated(
+        recognize(tuple((alpha1, space1, alpha1))),
+        alt((tag(" bags"), tag(" bag"))),
+    )(input)
+}
+
+impl Solution for Day7 {
+    type Input = BagsRules;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        parse_bags(input)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        part2(input)
+    }
+}
+
+pub fn part1(rules: &BagsRules) -> usize {
+    rules
+        .iter()
+        .filter(|(_, b)| contains_gold(rules, b))
+        .count()
+}
+
+fn contains_gold(rules: &BagsRules, bag: &Bag) -> bool {
+    bag.insides
+        .iter()
+        .any(|(_, k)| k == "shiny gold" || rules.get(k).is_some_and(|b| contains_gold(rules, b)))
+}
+
+pub fn part2(rules: &BagsRules) -> common::Result<u64> {
+    match rules.get("shiny gold") {
+        Some(bag) => contains_bags(rules, bag)
+            .ok_or_else(|| Error::Other(format!("more than {} bags", u64::MAX))),
+        None => no_solution(),
+    }
+}
+
+// None when the number of bags doesn't fit in a u64
+fn contains_bags(rules: &BagsRules, bag: &Bag) -> Option<u64> {
+    bag.insides.iter().try_fold(0u64, |total, (c, k)| {
+        let c = u64::from(*c);
+        let insides = rules.get(k).map_or(Some(0), |b| contains_bags(rules, b))?;
+        total.checked_add(c.checked_mul(insides)?.checked_add(c)?)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_invalid_rules() {
+        let rule = "shiny gold bags contain 2 dark red bags.\n";
+        assert_eq!(
+            parse_bags(rule).err(),
+            Some(Error::parse(1, "no rule for dark red bags"))
+        );
+        let rules = format!("{}dark red bags contain 1 shiny gold bag.", rule);
+        assert!(matches!(
+            parse_bags(&rules),
+            Err(Error::Parse { message, .. }) if message.ends_with("bags contain themselves")
+        ));
+        assert_eq!(
+            parse_bags(
+                "dark red bags contain no other bags.\nshiny gold bags hold 2 dark red bags."
+            )
+            .err(),
+            Some(Error::parse(
+                2,
+                "\"shiny gold bags hold 2 dark red bags.\": invalid rule"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let rules = "shiny gold bags contain 4000000000 dark red bags.\n\
+                     dark red bags contain 4000000000 dark blue bags.\n";
+        assert_eq!(
+            part2(&parse_bags(&format!("{}dark blue bags contain no other bags.", rules)).unwrap()),
+            Ok(16_000_000_004_000_000_000)
+        );
+        let rules = format!(
+            "{}dark blue bags contain 2 faded black bags.\nfaded black bags contain no other bags.",
+            rules
+        );
+        assert_eq!(
+            part2(&parse_bags(&rules).unwrap()),
+            Err(Error::Other(format!("more than {} bags", u64::MAX)))
+        );
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use day8::{parse_program, read_program};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(program) = read_program(data, |_| {}) {
+        // Disassembling and parsing again gives back the same program
+        assert_eq!(parse_program(&program.to_string()), Ok(program));
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use day8::{repair_program, Cpu, Program};
+
+fuzz_target!(|program: Program| {
+    if let Some(repaired) = repair_program(&program) {
+        assert_eq!(Cpu::default().run_program(&repaired).0, None);
+        let changed = program
+            .instructions
+            .iter()
+            .zip(repaired.instructions.iter())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(changed, 1);
+        assert_eq!(program.instructions.len(), repaired.instructions.len());
+    }
+});
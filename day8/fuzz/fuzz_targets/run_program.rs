@@ -0,0 +1,13 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use day8::{generate_endpoints, parse_program, Cpu, Program};
+
+fuzz_target!(|program: Program| {
+    let (looped, _) = Cpu::default().run_program(&program);
+    // The program terminates exactly when its first instruction reaches the end
+    assert_eq!(looped.is_none(), generate_endpoints(&program).contains(&0));
+    if !program.instructions.is_empty() {
+        assert_eq!(parse_program(&program.to_string()), Ok(program));
+    }
+});
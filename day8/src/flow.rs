@@ -0,0 +1,320 @@
+use std::collections::BTreeSet;
+
+use crate::{OpCode, Program};
+
+/// Control flow graph of a program, every instruction has exactly one
+/// successor and the end of the program is the extra node `exit`
+#[derive(Debug, Clone)]
+pub struct ControlFlow {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NaturalLoop {
+    pub header: usize,
+    pub back_edge: (usize, usize),
+    pub body: BTreeSet<usize>,
+}
+
+/// Why flipping the instruction at `pc` changes the outcome of the program
+#[derive(Debug, PartialEq, Clone)]
+pub struct FlipExplanation {
+    pub pc: usize,
+    pub flipped: OpCode,
+    pub target: usize,
+    // Back edges of the loops the flipped instruction now jumps out of
+    pub broken_back_edges: Vec<(usize, usize)>,
+    // Post dominators from target to the exit, None if target never terminates
+    pub escape: Option<Vec<usize>>,
+}
+
+impl ControlFlow {
+    pub fn new(program: &Program) -> Self {
+        let length = program.instructions.len();
+        let mut successors = vec![vec![]; length + 1];
+        let mut predecessors = vec![vec![]; length + 1];
+        for (ori, op) in program.instructions.iter().enumerate() {
+            let des = op.successor(ori, length);
+            successors[ori].push(des);
+            predecessors[des].push(ori);
+        }
+        ControlFlow {
+            successors,
+            predecessors,
+        }
+    }
+
+    pub const fn entry(&self) -> usize {
+        0
+    }
+
+    pub fn exit(&self) -> usize {
+        self.successors.len() - 1
+    }
+
+    pub fn successors(&self, node: usize) -> &[usize] {
+        &self.successors[node]
+    }
+
+    pub fn predecessors(&self, node: usize) -> &[usize] {
+        &self.predecessors[node]
+    }
+
+    // Immediate dominator of every node, the entry is its own dominator and
+    // nodes unreachable from the entry have none
+    pub fn dominators(&self) -> Vec<Option<usize>> {
+        immediate_dominators(self.entry(), &self.successors, &self.predecessors)
+    }
+
+    // Immediate post dominator of every node, the exit is its own post
+    // dominator and nodes that never terminate have none
+    pub fn post_dominators(&self) -> Vec<Option<usize>> {
+        immediate_dominators(self.exit(), &self.predecessors, &self.successors)
+    }
+
+    // Loops reachable from the entry, one for each back edge
+    pub fn natural_loops(&self) -> Vec<NaturalLoop> {
+        let dominators = self.dominators();
+        let mut loops = vec![];
+        for (node, successors) in self.successors.iter().enumerate() {
+            for &header in successors {
+                if dominates(&dominators, header, node) {
+                    loops.push(NaturalLoop {
+                        header,
+                        back_edge: (node, header),
+                        body: self.loop_body(&dominators, header, node),
+                    });
+                }
+            }
+        }
+        loops
+    }
+
+    // Reachable nodes that reach the tail of the back edge without passing the
+    // header
+    fn loop_body(
+        &self,
+        dominators: &[Option<usize>],
+        header: usize,
+        tail: usize,
+    ) -> BTreeSet<usize> {
+        let mut body = BTreeSet::new();
+        body.insert(header);
+        let mut nodes_left = vec![tail];
+        while let Some(x) = nodes_left.pop() {
+            if body.insert(x) {
+                nodes_left.extend(
+                    self.predecessors(x)
+                        .iter()
+                        .filter(|&&p| dominators[p].is_some()),
+                );
+            }
+        }
+        body
+    }
+
+    // Chain of post dominators from node to the exit
+    pub fn post_dominator_chain(&self, node: usize) -> Option<Vec<usize>> {
+        let post_dominators = self.post_dominators();
+        let mut chain = vec![node];
+        let mut x = node;
+        while x != self.exit() {
+            x = post_dominators[x]?;
+            chain.push(x);
+        }
+        Some(chain)
+    }
+}
+
+// Explain the change of the jmp/nop at pc: the loops it breaks and the region
+// it escapes into
+pub fn explain_flip(program: &Program, pc: usize) -> Option<FlipExplanation> {
+    let flipped = program.instructions.get(pc)?.change()?;
+    let target = flipped.successor(pc, program.instructions.len());
+    let flow = ControlFlow::new(program);
+    let broken_back_edges = flow
+        .natural_loops()
+        .into_iter()
+        .filter(|l| l.body.contains(&pc) && !l.body.contains(&target))
+        .map(|l| l.back_edge)
+        .collect();
+    Some(FlipExplanation {
+        pc,
+        flipped,
+        target,
+        broken_back_edges,
+        escape: flow.post_dominator_chain(target),
+    })
+}
+
+fn dominates(dominators: &[Option<usize>], dominator: usize, node: usize) -> bool {
+    if dominators[node].is_none() {
+        return false;
+    }
+    let mut x = node;
+    loop {
+        if x == dominator {
+            return true;
+        }
+        match dominators[x] {
+            Some(d) if d != x => x = d,
+            _ => return false,
+        }
+    }
+}
+
+// Cooper, Harvey and Kennedy "A Simple, Fast Dominance Algorithm"
+fn immediate_dominators(
+    root: usize,
+    successors: &[Vec<usize>],
+    predecessors: &[Vec<usize>],
+) -> Vec<Option<usize>> {
+    let postorder = postorder(root, successors);
+    let mut order = vec![None; successors.len()];
+    for (i, &node) in postorder.iter().enumerate() {
+        order[node] = Some(i);
+    }
+    let mut dominators = vec![None; successors.len()];
+    dominators[root] = Some(root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in postorder.iter().rev().filter(|&&n| n != root) {
+            let mut new_dominator = None;
+            for &pred in &predecessors[node] {
+                if dominators[pred].is_none() {
+                    continue;
+                }
+                new_dominator = Some(match new_dominator {
+                    None => pred,
+                    Some(d) => intersect(&dominators, &order, pred, d),
+                });
+            }
+            if new_dominator.is_some() && dominators[node] != new_dominator {
+                dominators[node] = new_dominator;
+                changed = true;
+            }
+        }
+    }
+    dominators
+}
+
+fn intersect(
+    dominators: &[Option<usize>],
+    order: &[Option<usize>],
+    mut a: usize,
+    mut b: usize,
+) -> usize {
+    while a != b {
+        while order[a] < order[b] {
+            a = dominators[a].unwrap();
+        }
+        while order[b] < order[a] {
+            b = dominators[b].unwrap();
+        }
+    }
+    a
+}
+
+fn postorder(root: usize, successors: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut postorder = vec![];
+    let mut stack = vec![(root, 0)];
+    visited[root] = true;
+    while let Some((node, next)) = stack.pop() {
+        if let Some(&child) = successors[node].get(next) {
+            stack.push((node, next + 1));
+            if !visited[child] {
+                visited[child] = true;
+                stack.push((child, 0));
+            }
+        } else {
+            postorder.push(node);
+        }
+    }
+    postorder
+}
+
+#[cfg(test)]
+mod test_flow {
+    use super::*;
+    use crate::fixture::example;
+    use crate::{parse_program, repair_program, strategy};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_dominators() {
+        let flow = ControlFlow::new(&parse_program(example()).unwrap());
+        assert_eq!(
+            flow.dominators(),
+            vec![
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(7),
+                Some(3),
+                None,
+                Some(2),
+                Some(6),
+                None,
+                None
+            ]
+        );
+        let mut post_dominators = vec![None; 10];
+        post_dominators[8] = Some(9);
+        post_dominators[9] = Some(9);
+        assert_eq!(flow.post_dominators(), post_dominators);
+    }
+
+    #[test]
+    fn test_natural_loops() {
+        let flow = ControlFlow::new(&parse_program(example()).unwrap());
+        assert_eq!(
+            flow.natural_loops(),
+            vec![NaturalLoop {
+                header: 1,
+                back_edge: (4, 1),
+                body: vec![1, 2, 3, 4, 6, 7].into_iter().collect(),
+            }]
+        );
+        let flow = ControlFlow::new(&parse_program("acc +1\njmp +0").unwrap());
+        assert_eq!(flow.natural_loops()[0].back_edge, (1, 1));
+        let flow = ControlFlow::new(&parse_program("jmp +2\njmp +0").unwrap());
+        assert_eq!(flow.natural_loops(), vec![]);
+    }
+
+    #[test]
+    fn test_explain_flip() {
+        let program = parse_program(example()).unwrap();
+        assert_eq!(
+            explain_flip(&program, 7),
+            Some(FlipExplanation {
+                pc: 7,
+                flipped: OpCode::Nop(-4),
+                target: 8,
+                broken_back_edges: vec![(4, 1)],
+                escape: Some(vec![8, 9]),
+            })
+        );
+        // The flipped jmp goes to 3, still inside the loop
+        let explanation = explain_flip(&program, 2).unwrap();
+        assert_eq!(explanation.target, 3);
+        assert_eq!(explanation.broken_back_edges, vec![]);
+        assert_eq!(explanation.escape, None);
+        assert_eq!(explain_flip(&program, 1), None);
+    }
+
+    proptest! {
+        #[test]
+        fn repaired_flip_escapes(program in strategy::program()) {
+            if let Some(repaired) = repair_program(&program) {
+                let pc = (0..program.instructions.len())
+                    .find(|&i| program.instructions[i] != repaired.instructions[i])
+                    .unwrap();
+                let explanation = explain_flip(&program, pc).unwrap();
+                prop_assert!(explanation.escape.is_some());
+            }
+        }
+    }
+}
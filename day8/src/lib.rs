@@ -0,0 +1,538 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::character::complete::{alpha0, char, digit1, space1};
+use nom::combinator::{all_consuming, map_res, recognize};
+use nom::sequence::tuple;
+use nom::{Finish, IResult};
+
+use common::{no_solution, Error, Solution};
+
+pub mod flow;
+pub mod minimize;
+
+pub struct Day8;
+
+#[derive(Default)]
+pub struct Cpu {
+    accumulator: isize,
+    program_counter: usize,
+}
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Program {
+    pub instructions: Vec<OpCode>,
+}
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum OpCode {
+    Acc(isize),
+    Jmp(isize),
+    Nop(isize),
+}
+
+/// Lines read between two progress reports while loading a program
+const PROGRESS_INTERVAL: usize = 100_000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub lines: usize,
+    pub bytes: usize,
+}
+
+/// Iterator over the instructions of a program read line by line, only the
+/// current line is kept in memory
+pub struct OpCodeReader<R> {
+    reader: R,
+    line: String,
+    progress: Progress,
+}
+
+impl Solution for Day8 {
+    type Input = Program;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Input) -> common::Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> common::Result<Self::Answer2> {
+        part2(input).map_or_else(no_solution, Ok)
+    }
+}
+
+pub fn part1(program: &Program) -> isize {
+    let (_, acu) = Cpu::default().run_program(program);
+    acu
+}
+
+pub fn part2(program: &Program) -> Option<isize> {
+    let program = repair_program(program)?;
+    let (_, acu) = Cpu::default().run_program(&program);
+    Some(acu)
+}
+
+// Find the first jmp/nop on the execution path that, once flipped, lands on
+// an instruction that reaches the end of the program. Only a program that
+// loops can be repaired.
+pub fn repair_program(program: &Program) -> Option<Program> {
+    let end_point = generate_endpoints(program);
+    if end_point.contains(&0) {
+        return None;
+    }
+    let length = program.instructions.len();
+    let mut instruction_viewed: HashSet<usize> = HashSet::new();
+    let mut cpu = Cpu::default();
+    while instruction_viewed.insert(cpu.program_counter) {
+        let op = cpu.get_instruction(program)?;
+        if let Some(changed_op) = op.change() {
+            let destiny = cpu.calculate_destination(&changed_op).min(length);
+
+            if end_point.contains(&destiny) {
+                let mut program = program.clone();
+                program.instructions[cpu.program_counter] = changed_op;
+                return Some(program);
+            }
+        }
+        cpu.tick(program);
+    }
+    None
+}
+
+// Flip every jmp/nop visited by the program, in execution order, and return
+// the first change that makes it terminate
+pub fn repair_brute_force(program: &Program) -> Option<Program> {
+    let (looped, _) = Cpu::default().run_program(program);
+    looped?;
+    let mut instruction_viewed: HashSet<usize> = HashSet::new();
+    let mut cpu = Cpu::default();
+    while instruction_viewed.insert(cpu.program_counter) {
+        let op = cpu.get_instruction(program)?;
+        if let Some(changed_op) = op.change() {
+            let mut changed = program.clone();
+            changed.instructions[cpu.program_counter] = changed_op;
+            if Cpu::default().run_program(&changed).0.is_none() {
+                return Some(changed);
+            }
+        }
+        cpu.tick(program);
+    }
+    None
+}
+
+// Instructions from which the program terminates, the end of the program is
+// represented by the index one past the last instruction
+pub fn generate_endpoints(program: &Program) -> HashSet<usize> {
+    let mut destinations: HashMap<usize, HashSet<usize>> = HashMap::new();
+    let length = program.instructions.len();
+    program
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(ori, op)| (ori, op.successor(ori, length)))
+        .for_each(|(origem, destino)| {
+            let des = destinations.get_mut(&destino);
+            if let Some(set) = des {
+                set.insert(origem);
+            } else {
+                let mut set = HashSet::new();
+                set.insert(origem);
+                destinations.insert(destino, set);
+            }
+        });
+    let mut end_points = HashSet::new();
+    let mut nodes_left = vec![];
+    nodes_left.push(program.instructions.len());
+    while let Some(x) = nodes_left.pop() {
+        end_points.insert(x);
+        destinations.get(&x).iter().for_each(|e| {
+            e.iter().for_each(|d| {
+                if !end_points.contains(d) {
+                    nodes_left.push(*d);
+                }
+            })
+        });
+    }
+    end_points
+}
+
+impl Cpu {
+    pub fn tick<'a>(&mut self, program: &'a Program) -> Option<&'a OpCode> {
+        let op = self.get_instruction(program)?;
+        self.accumulator = self.calcualte_accu(op);
+        self.program_counter = self.calculate_destination(op);
+        Some(op)
+    }
+
+    pub fn get_instruction<'a>(&self, program: &'a Program) -> Option<&'a OpCode> {
+        program.instructions.get(self.program_counter)
+    }
+
+    fn calcualte_accu(&self, op: &OpCode) -> isize {
+        if let OpCode::Acc(i) = op {
+            self.accumulator.wrapping_add(*i)
+        } else {
+            self.accumulator
+        }
+    }
+
+    fn calculate_destination(&self, op: &OpCode) -> usize {
+        match op {
+            OpCode::Acc(_) | OpCode::Nop(_) => self.program_counter + 1,
+            OpCode::Jmp(i) => if i.is_negative() {
+                self.program_counter
+                    .checked_sub(i.wrapping_abs() as usize)
+                    .or(Some(0))
+            } else {
+                self.program_counter.checked_add(*i as usize)
+            }
+            .unwrap_or(usize::MAX),
+        }
+    }
+
+    // Run the program and return if found loop and the last accumulator
+    pub fn run_program(mut self, program: &Program) -> (Option<()>, isize) {
+        let mut instruction_viewed: HashSet<usize> = HashSet::new();
+        loop {
+            if !instruction_viewed.insert(self.program_counter) {
+                break (Some(()), self.accumulator);
+            }
+            if self.tick(program).is_none() {
+                break (None, self.accumulator);
+            }
+        }
+    }
+}
+
+impl OpCode {
+    pub const fn change(&self) -> Option<Self> {
+        match self {
+            Self::Jmp(x) => Some(Self::Nop(*x)),
+            Self::Nop(x) => Some(Self::Jmp(*x)),
+            Self::Acc(_) => None,
+        }
+    }
+
+    // Next instruction executed after this one at position ori, any jump past
+    // the end of the program goes to length
+    pub fn successor(&self, ori: usize, length: usize) -> usize {
+        let des: isize = match self {
+            Self::Jmp(x) => (ori as isize).saturating_add(*x),
+            _ => (ori + 1) as isize,
+        };
+        match des {
+            x if x < 0 => 0,
+            x if x as usize >= length => length,
+            x => x as usize,
+        }
+    }
+}
+
+impl TryFrom<(&str, isize)> for OpCode {
+    type Error = ();
+
+    fn try_from(value: (&str, isize)) -> Result<Self, Self::Error> {
+        match value.0 {
+            "acc" => Ok(Self::Acc(value.1)),
+            "jmp" => Ok(Self::Jmp(value.1)),
+            "nop" => Ok(Self::Nop(value.1)),
+            _ => Err(()),
+        }
+    }
+}
+
+pub fn parse_program(input: &str) -> common::Result<Program> {
+    read_program(input.as_bytes(), |_| {})
+}
+
+// Read a whole program, calling progress every PROGRESS_INTERVAL lines and
+// once at the end
+pub fn read_program<R: BufRead>(
+    reader: R,
+    mut progress: impl FnMut(&Progress),
+) -> common::Result<Program> {
+    let mut op_codes = OpCodeReader::new(reader);
+    let mut instructions = Vec::new();
+    while let Some(op) = op_codes.next() {
+        instructions.push(op?);
+        if op_codes.progress.lines.is_multiple_of(PROGRESS_INTERVAL) {
+            progress(&op_codes.progress);
+        }
+    }
+    progress(&op_codes.progress);
+    // Reported on the last line read, the first one of an empty input
+    if instructions.is_empty() {
+        return Err(Error::parse(
+            op_codes.progress.lines.max(1),
+            "empty program",
+        ));
+    }
+    Ok(Program { instructions })
+}
+
+impl<R: BufRead> OpCodeReader<R> {
+    pub fn new(reader: R) -> Self {
+        OpCodeReader {
+            reader,
+            line: String::new(),
+            progress: Progress::default(),
+        }
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+}
+
+impl<R: BufRead> Iterator for OpCodeReader<R> {
+    type Item = common::Result<OpCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            let read = match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(read) => read,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.progress.lines += 1;
+            self.progress.bytes += read;
+            let line = self.line.trim_end_matches(&['\n', '\r'][..]);
+            if line.is_empty() {
+                continue;
+            }
+            return Some(match all_consuming(parse_op)(line).finish() {
+                Ok((_, op)) => Ok(op),
+                Err(_) => Err(Error::parse(
+                    self.progress.lines,
+                    format!("invalid instruction {:?}", line),
+                )),
+            });
+        }
+    }
+}
+
+pub fn parse_op(input: &str) -> IResult<&str, OpCode> {
+    map_res(tuple((alpha0, space1, parse_isize)), |(f, _, i)| {
+        OpCode::try_from((f, i))
+    })(input)
+}
+
+fn parse_isize(input: &str) -> IResult<&str, isize> {
+    map_res(
+        recognize(tuple((alt((char('-'), char('+'))), digit1))),
+        FromStr::from_str,
+    )(input)
+}
+
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Acc(x) => write!(f, "acc {:+}", x),
+            Self::Jmp(x) => write!(f, "jmp {:+}", x),
+            Self::Nop(x) => write!(f, "nop {:+}", x),
+        }
+    }
+}
+
+// Disassemble the program in the same format read by parse_program
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, op) in self.instructions.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", op)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_opcode_parse() {
+        let (_, d) = parse_op("acc -9").unwrap();
+        assert_eq!(d, OpCode::Acc(-9));
+        let (_, d) = parse_op("nop -9").unwrap();
+        assert_eq!(d, OpCode::Nop(-9));
+        let (_, d) = parse_op("jmp -9").unwrap();
+        assert_eq!(d, OpCode::Jmp(-9));
+        let (_, d) = parse_op("jmp +9").unwrap();
+        assert_eq!(d, OpCode::Jmp(9));
+    }
+
+    #[test]
+    fn test_example_1() {
+        let program = parse_program(fixture::example()).unwrap();
+        let result = Cpu::default().run_program(&program);
+        assert_eq!(result, (Some(()), 5));
+    }
+
+    #[test]
+    fn test_example_2() {
+        let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\nnop -4\nacc +6";
+        let program = parse_program(input).unwrap();
+        let result = Cpu::default().run_program(&program);
+        assert_eq!(result, (None, 8));
+    }
+
+    #[test]
+    fn test_read_program() {
+        let input = "nop +0\r\nacc +1\n\njmp -2\n";
+        let mut reports = vec![];
+        let program = read_program(input.as_bytes(), |p| reports.push(*p)).unwrap();
+        assert_eq!(
+            program.instructions,
+            vec![OpCode::Nop(0), OpCode::Acc(1), OpCode::Jmp(-2)]
+        );
+        assert_eq!(
+            reports,
+            vec![Progress {
+                lines: 4,
+                bytes: 23
+            }]
+        );
+        assert_eq!(
+            parse_program("nop +0\nacc 1").unwrap_err(),
+            Error::parse(2, "invalid instruction \"acc 1\"")
+        );
+        assert_eq!(
+            parse_program("").unwrap_err(),
+            Error::parse(1, "empty program")
+        );
+        assert_eq!(parse_program("\n\n").unwrap_err().exit_code(), 65);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let input = "nop +0\nacc +1\njmp -4\nacc -99";
+        let program = parse_program(input).unwrap();
+        assert_eq!(program.to_string(), input);
+        assert_eq!(parse_program(&program.to_string()), Ok(program));
+    }
+
+    #[test]
+    fn test_repair() {
+        let program = repair_program(&parse_program(fixture::example()).unwrap()).unwrap();
+        assert_eq!(program.instructions[7], OpCode::Nop(-4));
+        assert_eq!(Cpu::default().run_program(&program), (None, 8));
+        // The last instruction loops, flipping it is the only way out
+        let program = parse_program("acc +1\njmp +0").unwrap();
+        let program = repair_program(&program).unwrap();
+        assert_eq!(Cpu::default().run_program(&program), (None, 1));
+        let program = parse_program("acc +1\njmp -1").unwrap();
+        let program = repair_program(&program).unwrap();
+        assert_eq!(Cpu::default().run_program(&program), (None, 1));
+        assert_eq!(
+            repair_program(&parse_program("jmp +0\njmp -1").unwrap()),
+            None
+        );
+        assert_eq!(
+            repair_program(&parse_program("nop +1\njmp +2").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let program = Program {
+            instructions: vec![
+                OpCode::Acc(isize::MAX),
+                OpCode::Acc(1),
+                OpCode::Jmp(isize::MAX),
+            ],
+        };
+        assert_eq!(Cpu::default().run_program(&program), (None, isize::MIN));
+        let program = Program {
+            instructions: vec![OpCode::Nop(0), OpCode::Jmp(isize::MIN)],
+        };
+        assert_eq!(Cpu::default().run_program(&program), (Some(()), 0));
+        assert_eq!(
+            generate_endpoints(&Program {
+                instructions: vec![]
+            })
+            .len(),
+            1
+        );
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixture {
+    // Program of the puzzle example, after the answers in its examples file
+    pub fn example() -> &'static str {
+        let text = include_str!("../examples/example.txt");
+        let separator = "\n---\n";
+        &text[text.find(separator).unwrap() + separator.len()..]
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod strategy {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn offset() -> impl Strategy<Value = isize> {
+        prop_oneof![
+            8 => -12isize..12,
+            1 => Just(isize::MIN),
+            1 => Just(isize::MAX),
+        ]
+    }
+
+    fn op_code() -> impl Strategy<Value = OpCode> {
+        prop_oneof![
+            offset().prop_map(OpCode::Acc),
+            offset().prop_map(OpCode::Jmp),
+            offset().prop_map(OpCode::Nop),
+        ]
+    }
+
+    pub fn program() -> impl Strategy<Value = Program> {
+        prop::collection::vec(op_code(), 1..40).prop_map(|instructions| Program { instructions })
+    }
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn endpoints_agree_with_run(program in strategy::program()) {
+            let (looped, _) = Cpu::default().run_program(&program);
+            prop_assert_eq!(looped.is_none(), generate_endpoints(&program).contains(&0));
+        }
+
+        #[test]
+        fn repair_agrees_with_brute_force(program in strategy::program()) {
+            let guided = repair_program(&program);
+            let brute_force = repair_brute_force(&program);
+            prop_assert_eq!(&guided, &brute_force);
+            if let Some(repaired) = guided {
+                let (looped, guided_acc) = Cpu::default().run_program(&repaired);
+                prop_assert_eq!(looped, None);
+                let (_, brute_force_acc) = Cpu::default().run_program(&brute_force.unwrap());
+                prop_assert_eq!(guided_acc, brute_force_acc);
+            }
+        }
+
+        #[test]
+        fn disassemble_round_trip(program in strategy::program()) {
+            prop_assert_eq!(parse_program(&program.to_string()), Ok(program));
+        }
+    }
+}
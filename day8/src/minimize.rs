@@ -0,0 +1,184 @@
+use crate::{OpCode, Program};
+
+// Smallest program found with the same outcome and accumulator: instructions
+// unreachable from the start are dropped, nops and jmp +1 removed and
+// consecutive accs merged, jumps are rewritten to keep their destination. A
+// program left empty is a single nop +0 so that it can be parsed again. The
+// repair of the minimized program may differ since the removed nops could be
+// flipped.
+pub fn minimize(program: &Program) -> Program {
+    let length = program.instructions.len();
+    let reachable = reachable(program);
+    // Instructions entered by a jump can't be merged with the previous one
+    let mut leaders = vec![false; length + 1];
+    leaders[0] = true;
+    for (ori, op) in program.instructions.iter().enumerate() {
+        if reachable[ori] {
+            if let OpCode::Jmp(_) = op {
+                leaders[op.successor(ori, length)] = true;
+            }
+        }
+    }
+
+    // new_position[ori] is where execution continues in the minimized
+    // program, it's only meaningful for leaders and the end of the program
+    let mut new_position = vec![0; length + 1];
+    let mut instructions: Vec<OpCode> = vec![];
+    let mut jumps = vec![];
+    let mut merge_acc = false;
+    for (ori, op) in program.instructions.iter().enumerate() {
+        if !reachable[ori] || leaders[ori] {
+            merge_acc = false;
+        }
+        if !reachable[ori] {
+            continue;
+        }
+        new_position[ori] = instructions.len();
+        match op {
+            OpCode::Nop(_) => {}
+            OpCode::Acc(x) => match instructions.last_mut() {
+                Some(OpCode::Acc(last)) if merge_acc => *last = last.wrapping_add(*x),
+                _ => {
+                    instructions.push(OpCode::Acc(*x));
+                    merge_acc = true;
+                }
+            },
+            OpCode::Jmp(_) => {
+                jumps.push((instructions.len(), op.successor(ori, length)));
+                instructions.push(OpCode::Jmp(0));
+                merge_acc = false;
+            }
+        }
+    }
+    new_position[length] = instructions.len();
+
+    for (position, destination) in jumps {
+        let offset = new_position[destination] as isize - position as isize;
+        instructions[position] = OpCode::Jmp(offset);
+    }
+    drop_jumps_to_next(&mut instructions);
+    if instructions.is_empty() {
+        instructions.push(OpCode::Nop(0));
+    }
+    Program { instructions }
+}
+
+// Remove the jmp +1, merging the accs around one when no jump enters the
+// second acc
+fn drop_jumps_to_next(instructions: &mut Vec<OpCode>) {
+    while let Some(p) = instructions.iter().position(|op| *op == OpCode::Jmp(1)) {
+        remove(instructions, p);
+        if p == 0 || p >= instructions.len() || is_jump_target(instructions, p) {
+            continue;
+        }
+        if let (&OpCode::Acc(a), &OpCode::Acc(b)) = (&instructions[p - 1], &instructions[p]) {
+            instructions[p - 1] = OpCode::Acc(a.wrapping_add(b));
+            remove(instructions, p);
+        }
+    }
+}
+
+// Remove the instruction at p, the jumps to it go to the next one
+fn remove(instructions: &mut Vec<OpCode>, p: usize) {
+    instructions.remove(p);
+    for (position, op) in instructions.iter_mut().enumerate() {
+        if let OpCode::Jmp(offset) = op {
+            let before = if position >= p {
+                position + 1
+            } else {
+                position
+            };
+            let destination = before as isize + *offset;
+            let destination = if destination > p as isize {
+                destination - 1
+            } else {
+                destination
+            };
+            *offset = destination - position as isize;
+        }
+    }
+}
+
+fn is_jump_target(instructions: &[OpCode], p: usize) -> bool {
+    instructions
+        .iter()
+        .enumerate()
+        .any(|(position, op)| match op {
+            OpCode::Jmp(offset) => position as isize + offset == p as isize,
+            _ => false,
+        })
+}
+
+fn reachable(program: &Program) -> Vec<bool> {
+    let length = program.instructions.len();
+    let mut reachable = vec![false; length];
+    let mut pc = 0;
+    while pc < length && !reachable[pc] {
+        reachable[pc] = true;
+        pc = program.instructions[pc].successor(pc, length);
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod test_minimize {
+    use super::*;
+    use crate::fixture::example;
+    use crate::{parse_program, strategy, Cpu};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_example() {
+        let program = minimize(&parse_program(example()).unwrap());
+        assert_eq!(
+            program.to_string(),
+            "acc +1\njmp +3\nacc +3\njmp -3\nacc +1\njmp -3"
+        );
+        assert_eq!(Cpu::default().run_program(&program), (Some(()), 5));
+    }
+
+    #[test]
+    fn test_merge() {
+        let input = "acc +1\nnop +5\nacc +2\njmp +2\nacc +7\nacc +3\nnop +0\nacc -1";
+        let program = minimize(&parse_program(input).unwrap());
+        assert_eq!(program.to_string(), "acc +5");
+        let input = "acc +1\nacc +2\njmp +2\njmp -2\nnop +0";
+        let program = minimize(&parse_program(input).unwrap());
+        assert_eq!(program.to_string(), "acc +3");
+        let input = "acc +1\nnop +0\nacc +2\njmp -2";
+        let program = minimize(&parse_program(input).unwrap());
+        assert_eq!(program.to_string(), "acc +1\nacc +2\njmp -1");
+        let program = minimize(&parse_program("nop +0\nnop +0").unwrap());
+        assert_eq!(program.instructions, vec![OpCode::Nop(0)]);
+        assert_eq!(Cpu::default().run_program(&program), (None, 0));
+    }
+
+    #[test]
+    fn test_jumps_to_next() {
+        let program = minimize(&parse_program("jmp +1\njmp +1").unwrap());
+        assert_eq!(program.instructions, vec![OpCode::Nop(0)]);
+        // The first jump lands on the removed jmp +1 and becomes one itself
+        let input = "acc +1\njmp +2\njmp +1\nacc +2\njmp -1";
+        let program = minimize(&parse_program(input).unwrap());
+        assert_eq!(program.to_string(), "acc +1\nacc +2\njmp -1");
+        // A jump still enters the second acc
+        let input = "acc +1\njmp +1\nacc +2\njmp -1";
+        let program = minimize(&parse_program(input).unwrap());
+        assert_eq!(program.to_string(), "acc +1\nacc +2\njmp -1");
+    }
+
+    proptest! {
+        #[test]
+        fn minimize_keeps_behavior(program in strategy::program()) {
+            let minimized = minimize(&program);
+            prop_assert!(minimized.instructions.len() <= program.instructions.len());
+            prop_assert_eq!(
+                Cpu::default().run_program(&program),
+                Cpu::default().run_program(&minimized)
+            );
+            prop_assert_eq!(minimize(&minimized), minimized.clone());
+            prop_assert!(!minimized.instructions.contains(&OpCode::Jmp(1)));
+            prop_assert_eq!(parse_program(&minimized.to_string()), Ok(minimized));
+        }
+    }
+}